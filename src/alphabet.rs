@@ -0,0 +1,156 @@
+use std::fmt::Debug;
+
+use crate::ngramweights::{check_decay_factor, decayed_count, NGramWeights};
+use crate::{Counter, NameExperiments, ValidChar, WeightStore};
+
+const VALID_CHAR_COUNT: usize = ValidChar::VARIANTCOUNT as usize;
+
+/// A `WeightStore` limited to an alphabet of valid characters. Created through `NameExperiments::with_alphabet`.
+///
+/// Only sequences made up of the alphabet and `ValidChar::null` are given a row, so an alphabet of `k` characters holds `(k + 1)^N` rows rather than `31^N`.
+/// The null character takes slot `0` of every sequence and stands for both the padding before a name and its end. Characters outside the alphabet are never counted or generated.
+/// `C` is the `Counter` every observation is counted in, defaulting to `u8`.
+#[derive(Debug, Clone)]
+pub struct AlphabetWeights<const N: usize, C = u8> {
+    /// The slot of every valid character within a sequence, or `None` if it is outside the alphabet
+    slots: [Option<usize>; VALID_CHAR_COUNT],
+    slot_count: usize,
+    weights: Vec<[C; VALID_CHAR_COUNT]>,
+    sum: Vec<usize>,
+}
+
+impl<const N: usize, C: Counter> AlphabetWeights<N, C> {
+    /// Create an empty store for `alphabet`. Null is always given slot `0`. Produces an Err if the rows of the store can not be indexed
+    fn with_alphabet(alphabet: &[ValidChar]) -> Result<Self, String> {
+        let mut slots = [None; VALID_CHAR_COUNT];
+        slots[ValidChar::null as usize] = Some(0);
+        let mut slot_count = 1usize;
+        for &ch in alphabet.iter().filter(|&&ch| ch != ValidChar::null) {
+            if slots[ch as usize].is_none() {
+                slots[ch as usize] = Some(slot_count);
+                slot_count += 1;
+            }
+        }
+        let rows = slot_count.checked_pow(N as u32).ok_or(format!("Number of {N} ngrams picked will result in overflow"))?;
+        Ok(AlphabetWeights {
+            slots,
+            slot_count,
+            weights: vec![[C::default(); VALID_CHAR_COUNT]; rows],
+            sum: vec![0; rows],
+        })
+    }
+    /// The number of rows held by the store
+    pub fn row_count(&self) -> usize {
+        self.weights.len()
+    }
+    /// The row of `char_seq`, or `None` if any of its first `N` values is outside the alphabet. Produces an Err if fewer than `N` values are given
+    fn row_index<T>(&self, char_seq: &[T]) -> Result<Option<usize>, String>
+        where usize: From<T>, T: Clone + Copy + Debug
+    {
+        if char_seq.len() < N {return Err("Not enough characters given to determine row".to_string())}
+        let mut index = 0usize;
+        for (i, &ch) in char_seq.iter().enumerate().take(N) {
+            let Some(&Some(slot)) = self.slots.get(usize::from(ch)) else {return Ok(None)};
+            index += self.slot_count.pow(i as u32) * slot;
+        }
+        Ok(Some(index))
+    }
+}
+
+impl<const N: usize, C: Counter> WeightStore<VALID_CHAR_COUNT> for AlphabetWeights<N, C> {
+    type Counter = C;
+
+    /// Create an empty store whose alphabet holds every valid character
+    fn new() -> Self {
+        Self::try_new().unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Create an empty store whose alphabet holds every valid character, producing an Err rather than panicking if the rows of the store can not be indexed
+    fn try_new() -> Result<Self, String> {
+        Self::with_alphabet(&ValidChar::ALLCHARS)
+    }
+    /// Create an empty store with the same alphabet
+    fn empty_like(&self) -> Result<Self, String> {
+        Ok(AlphabetWeights {
+            slots: self.slots,
+            slot_count: self.slot_count,
+            weights: vec![[C::default(); VALID_CHAR_COUNT]; self.weights.len()],
+            sum: vec![0; self.sum.len()],
+        })
+    }
+    fn get_row<T>(&self, char_seq: &[T]) -> Result<[C; VALID_CHAR_COUNT], String>
+        where usize: From<T>, T: Clone + Copy + Debug
+    {
+        Ok(self.row_index(char_seq)?.map_or([C::default(); VALID_CHAR_COUNT], |index| self.weights[index]))
+    }
+    fn get_mut_row<T>(&mut self, char_seq: &[T]) -> Result<&mut [C; VALID_CHAR_COUNT], String>
+        where usize: From<T>, T: Clone + Copy + Debug
+    {
+        let index = self.row_index(char_seq)?.ok_or(format!("{char_seq:?} is not made up of the alphabet"))?;
+        Ok(&mut self.weights[index])
+    }
+    fn add<T>(&mut self, sequence: &[T], following_char: &T) -> Result<(), String>
+        where usize: From<T>, T: Clone + Copy + Debug
+    {
+        let index = self.row_index(sequence)?.ok_or(format!("{sequence:?} is not made up of the alphabet"))?;
+        let column = usize::from(*following_char);
+        if !self.contains(column) {
+            return Err(format!("{following_char:?} is not in the alphabet"));
+        }
+        let row = &mut self.weights[index];
+        row[column] = row[column].checked_increment().ok_or("Weights max capacity reached")?;
+        self.sum[index] = self.sum[index].checked_add(1).ok_or("Max ngram experiments reached")?;
+        Ok(())
+    }
+    fn sum<T>(&self, char_seq: &[T]) -> Result<usize, String>
+        where usize: From<T>, T: Clone + Copy + Debug
+    {
+        Ok(self.row_index(char_seq)?.map_or(0, |index| self.sum[index]))
+    }
+    fn contains(&self, value: usize) -> bool {
+        self.slots.get(value).is_some_and(|slot| slot.is_some())
+    }
+    /// Zeroes every row and sum, keeping the alphabet and reusing the existing allocations
+    fn reset(&mut self) {
+        self.weights.fill([C::default(); VALID_CHAR_COUNT]);
+        self.sum.fill(0);
+    }
+    fn decay(&mut self, factor: f64) -> Result<(), String> {
+        check_decay_factor(factor)?;
+        for (row, sum) in self.weights.iter_mut().zip(self.sum.iter_mut()) {
+            if *sum == 0 {continue;}
+            for weight in row.iter_mut() {
+                *weight = C::from_usize(decayed_count(weight.to_usize(), factor)).unwrap_or(*weight);
+            }
+            *sum = row.iter().map(|weight| weight.to_usize()).sum();
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize, C: Counter> NameExperiments<N, AlphabetWeights<N, C>> {
+    /// Create a naming experiment limited to `alphabet`, e.g. for a naming scheme that never uses 'q', 'x' or 'z'. Characters outside the alphabet are skipped while reading
+    /// (the observations they take part in are not counted) and are never generated. The character weights shrink with the alphabet, see `AlphabetWeights`.
+    ///
+    /// Produces an Err if a character of `alphabet` is not a `ValidChar`, stands for the end of a name, or appears more than once (ignoring case and accents).
+    /// Also produces an Err under the same conditions as `NameExperiments::try_new`.
+    pub fn with_alphabet(alphabet: &[char]) -> Result<Self, String> {
+        let mut chars: Vec<ValidChar> = Vec::with_capacity(alphabet.len());
+        for c in alphabet.iter() {
+            let ch = ValidChar::try_from(c)?;
+            if ch == ValidChar::null {
+                return Err(format!("{c} stands for the end of a name and is always part of the alphabet"));
+            }
+            if chars.contains(&ch) {
+                return Err(format!("{c} appears more than once in the alphabet"));
+            }
+            chars.push(ch);
+        }
+        // The full alphabet is never allocated, so the experiment only ever takes the memory of the reduced weights
+        Self::from_weights(
+            AlphabetWeights::with_alphabet(&chars)?,
+            AlphabetWeights::with_alphabet(&chars)?,
+            NGramWeights::try_new()?,
+            NGramWeights::try_new()?,
+        )
+    }
+}
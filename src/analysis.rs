@@ -0,0 +1,143 @@
+use crate::{CharType, NameExperiments, ValidChar};
+
+const VALID_CHAR_COUNT: usize = ValidChar::VARIANTCOUNT as usize;
+
+/// The estimated probability of one character following a character sequence, paired with a confidence interval. Produced by `NameExperiments::transition_confidence`.
+/// 
+/// A wide interval indicates that the transition is guessed from few observations.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TransitionEstimate {
+    /// The number of times the character was observed following the character sequence
+    pub observations: u8,
+    /// `observations` divided by the number of observations of the character sequence. `0.0` if the character sequence was never observed
+    pub probability: f64,
+    /// The lower bound of the Wilson score interval
+    pub lower: f64,
+    /// The upper bound of the Wilson score interval
+    pub upper: f64,
+}
+
+/// [Wilson score interval](https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval#Wilson_score_interval) of `successes` out of `trials`. `(0.0, 1.0)` when there are no trials.
+fn wilson_interval(successes: usize, trials: usize, z: f64) -> (f64, f64) {
+    if trials == 0 {return (0.0, 1.0);}
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let z2 = z * z;
+    let centre = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+    let margin = (z / (1.0 + z2 / n)) * ((p * (1.0 - p) / n) + (z2 / (4.0 * n * n))).sqrt();
+    ((centre - margin).max(0.0), (centre + margin).min(1.0))
+}
+
+/// Laplace smoothed probability distribution of the characters following the context at `index` in the positive character weights.
+fn smoothed_positive_row<const N: usize>(experiments: &NameExperiments<N>, index: usize) -> [f64; VALID_CHAR_COUNT] {
+    let row = &experiments.positive_char_samples.weights[index];
+    let sum = experiments.positive_char_samples.sum[index];
+    let mut distribution = [0.0; VALID_CHAR_COUNT];
+    for (p, &w) in distribution.iter_mut().zip(row.iter()) {
+        *p = (w as f64 + 1.0) / (sum as f64 + VALID_CHAR_COUNT as f64);
+    }
+    distribution
+}
+
+/// Jensen-Shannon divergence (base 2) between two probability distributions. Bounded between `0.0` and `1.0`.
+fn jensen_shannon(p: &[f64], q: &[f64]) -> f64 {
+    let kl_to_mean = |a: &[f64], b: &[f64]| -> f64 {
+        a.iter().zip(b.iter()).map(|(&x, &y)| {
+            let m = (x + y) / 2.0;
+            if x == 0.0 {0.0} else {x * (x / m).log2()}
+        }).sum::<f64>()
+    };
+    (kl_to_mean(p, q) + kl_to_mean(q, p)) / 2.0
+}
+
+impl<const N: usize> NameExperiments<N> {
+    /// Classifies every character of `text` exactly as reading it as a sample would, each character within the window of the characters before it.
+    /// Useful to see why a name is learned the way it is, e.g. whether the "h" of a "ch" is read as an affricate.
+    /// 
+    /// Reading stops at `ValidChar::TERMINATOR`. Syllable breaks are classified like any other character. Produces an Err if `text` contains a character that is not a `ValidChar`.
+    pub fn char_types_of(&self, text: &str) -> Result<Vec<CharType>, String> {
+        let valid_chars: Vec<ValidChar> = text.chars().take_while(|&c| !ValidChar::is_terminator(c))
+            .map(|c| ValidChar::try_from(&c).map_err(|_| format!("{c} is not a valid character")))
+            .collect::<Result<_, _>>()?;
+        CharType::classify_all(&valid_chars)
+    }
+    /// Compares the positive and negative observations of a character sequence. Produces `(positive_sum, negative_sum)`, the number of characters observed following `char_seq`
+    /// in positive and in negative samples. Contexts where the negative sum overwhelms the positive sum cause characters to vanish from generation.
+    /// 
+    /// ## Parameters
+    /// * char_seq: an array slice of ValidChar to be analysed. Minimum length should be N.
+    pub fn context_balance(&self, char_seq: &[ValidChar]) -> Result<(usize, usize), String> {
+        let (_, positive_sum) = self.positive_char_samples.get_row_and_sum(char_seq)?;
+        let (_, negative_sum) = self.negative_char_samples.get_row_and_sum(char_seq)?;
+        Ok((positive_sum, negative_sum))
+    }
+    /// Produces a `TransitionEstimate` for every valid character that could follow `char_seq`, indexed like `ValidChar::ALLCHARS`. Only positive observations are considered and no easing is applied.
+    /// 
+    /// ## Parameters
+    /// * char_seq: an array slice of ValidChar to be analysed. Minimum length should be N.
+    /// * z: The standard score of the desired confidence level. E.g. `1.96` for 95% confidence
+    pub fn transition_confidence(&self, char_seq: &[ValidChar], z: f64) -> Result<[TransitionEstimate; VALID_CHAR_COUNT], String> {
+        let (row, sum) = self.positive_char_samples.get_row_and_sum(char_seq)?;
+        let mut estimates = [TransitionEstimate {observations: 0, probability: 0.0, lower: 0.0, upper: 1.0}; VALID_CHAR_COUNT];
+        for (estimate, &observations) in estimates.iter_mut().zip(row.iter()) {
+            let (lower, upper) = wilson_interval(observations as usize, sum, z);
+            *estimate = TransitionEstimate {
+                observations,
+                probability: if sum == 0 {0.0} else {observations as f64 / sum as f64},
+                lower,
+                upper,
+            };
+        }
+        Ok(estimates)
+    }
+    /// Measures how different the positive character distributions of two experiments are. Produces the average Jensen-Shannon divergence (base 2) across every context
+    /// observed in either experiment. `0.0` means the two experiments predict identical characters, `1.0` means they share nothing.
+    /// 
+    /// Each context's distribution is smoothed with the [Rule of Succession](https://en.wikipedia.org/wiki/Rule_of_succession), so a context that was only observed by one of the experiments is
+    /// compared against a uniform distribution. Negative samples are not considered. Produces `0.0` if neither experiment has read a positive sample.
+    pub fn divergence_from(&self, other: &NameExperiments<N>) -> f64 {
+        let mut total = 0.0;
+        let mut contexts = 0usize;
+        for index in 0..self.positive_char_samples.sum.len() {
+            if self.positive_char_samples.sum[index] == 0 && other.positive_char_samples.sum[index] == 0 {continue;}
+            total += jensen_shannon(&smoothed_positive_row(self, index), &smoothed_positive_row(other, index));
+            contexts += 1;
+        }
+        if contexts == 0 {0.0} else {total / contexts as f64}
+    }
+    /// Aggregates the positive character weights into a plain bigram frequency table, independent of `N`. `bigram_counts()[a][b]` is the number of times the character at index `b`
+    /// of `ValidChar::ALLCHARS` was observed directly after the character at index `a`, summed over every context. Useful for comparing against published letter-pair frequencies.
+    /// 
+    /// The start of a name is represented by `ValidChar::null` preceding the first character, and the end of a name by `ValidChar::null` following the last character.
+    /// Counts are widened to `u32`, so the aggregate is not capped by the `u8` weights.
+    pub fn bigram_counts(&self) -> [[u32; VALID_CHAR_COUNT]; VALID_CHAR_COUNT] {
+        let mut counts = [[0u32; VALID_CHAR_COUNT]; VALID_CHAR_COUNT];
+        for (index, row) in self.positive_char_samples.weights.iter().enumerate() {
+            if self.positive_char_samples.sum[index] == 0 {continue;}
+            // The most recent character of a context is its last element
+            let previous = self.positive_char_samples.get_row_sequence(index)[N - 1];
+            for (count, &w) in counts[previous].iter_mut().zip(row.iter()) {
+                *count += w as u32;
+            }
+        }
+        counts
+    }
+    /// Measures how alike two characters behave as the preceding character, e.g. whether "k" and "g" are followed by the same characters. Produces `1.0` minus the
+    /// Jensen-Shannon divergence (base 2) between the distributions of characters observed following `first` and following `second`, so `1.0` means the characters are interchangeable
+    /// and `0.0` means they are never followed by the same character.
+    /// 
+    /// The rest of each context is marginalized, i.e. the distributions are read from the rows of `bigram_counts`. Only positive observations are considered.
+    /// Produces `0.0` if either character was never observed followed by another character.
+    pub fn char_behavior_similarity(&self, first: ValidChar, second: ValidChar) -> f64 {
+        let bigrams = self.bigram_counts();
+        let distribution = |ch: ValidChar| -> Option<Vec<f64>> {
+            let row = &bigrams[ch as usize];
+            let total: u32 = row.iter().sum();
+            if total == 0 {None} else {Some(row.iter().map(|&count| count as f64 / total as f64).collect())}
+        };
+        match (distribution(first), distribution(second)) {
+            (Some(p), Some(q)) => 1.0 - jensen_shannon(&p, &q),
+            _ => 0.0,
+        }
+    }
+}
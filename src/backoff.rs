@@ -0,0 +1,87 @@
+use crate::{Counter, NameExperiments, ValidChar, WeightStore};
+
+const VALID_CHAR_COUNT: usize = ValidChar::VARIANTCOUNT as usize;
+
+/// Positive character observations following every context shorter than `N` characters, derived from the positive character weights.
+/// Order `k` holds the observations following each context of `k` characters, so order `0` holds how often each character was observed at all.
+pub(crate) struct LowerOrderCounts {
+    orders: Vec<(Vec<[usize; VALID_CHAR_COUNT]>, Vec<usize>)>,
+}
+
+impl LowerOrderCounts {
+    /// The index of the last `k` characters of `char_seq` within order `k`
+    fn suffix_index(char_seq: &[ValidChar], k: usize) -> usize {
+        char_seq[char_seq.len() - k..].iter().enumerate().map(|(i, &ch)| VALID_CHAR_COUNT.pow(i as u32) * ch as usize).sum()
+    }
+    /// Sums the observations of every full context into the orders below `N`. An observation following a context is also an observation following each of its suffixes
+    fn from_store<const N: usize, S: WeightStore<VALID_CHAR_COUNT>>(store: &S) -> Self {
+        let mut orders: Vec<(Vec<[usize; VALID_CHAR_COUNT]>, Vec<usize>)> = (0..N)
+            .map(|k| (vec![[0; VALID_CHAR_COUNT]; VALID_CHAR_COUNT.pow(k as u32)], vec![0; VALID_CHAR_COUNT.pow(k as u32)]))
+            .collect();
+        for index in 0..VALID_CHAR_COUNT.pow(N as u32) {
+            let mut context = [ValidChar::null; N];
+            let mut remainder = index;
+            for ch in context.iter_mut() {
+                *ch = ValidChar::ALLCHARS[remainder % VALID_CHAR_COUNT];
+                remainder /= VALID_CHAR_COUNT;
+            }
+            let (Ok(row), Ok(sum)) = (store.get_row(&context), store.sum(&context)) else {continue};
+            if sum == 0 {continue;}
+            for (k, (rows, sums)) in orders.iter_mut().enumerate() {
+                let suffix = Self::suffix_index(&context, k);
+                for (observations, &weight) in rows[suffix].iter_mut().zip(row.iter()) {
+                    *observations += weight.to_usize();
+                }
+                sums[suffix] += sum;
+            }
+        }
+        LowerOrderCounts { orders }
+    }
+}
+
+impl<const N: usize, S: WeightStore<{ValidChar::VARIANTCOUNT as usize}>> NameExperiments<N, S> {
+    /// The positive observations of each valid character following `char_seq`, and their sum, with back-off applied (see `GenerationConfig::backoff_threshold`).
+    ///
+    /// Working up from order `0` to the full context, a context observed fewer than `backoff_threshold` times keeps its own observations and is topped up to `backoff_threshold`
+    /// observations in the proportions estimated for the context one character shorter. A context that is observed often enough is left as it is.
+    pub(crate) fn positive_observations(&self, char_seq: &[ValidChar]) -> Result<([f64; VALID_CHAR_COUNT], f64), String> {
+        let row = self.positive_char_samples.get_row(char_seq)?;
+        let sum = self.positive_char_samples.sum(char_seq)?;
+        let observations = row.map(|weight| weight.to_usize() as f64);
+        let threshold = self.generation_config.backoff_threshold;
+        if threshold == 0 || sum >= threshold {
+            return Ok((observations, sum as f64));
+        }
+        let mut shorter_estimate: Option<[f64; VALID_CHAR_COUNT]> = None;
+        for k in 0..N {
+            let (lower_observations, lower_sum) = self.lower_order_observations(char_seq, k)?;
+            let (estimate, estimate_sum) = Self::back_off(lower_observations, lower_sum, threshold, shorter_estimate);
+            if estimate_sum > 0.0 {
+                shorter_estimate = Some(estimate.map(|weight| weight / estimate_sum));
+            }
+        }
+        Ok(Self::back_off(observations, sum, threshold, shorter_estimate))
+    }
+    /// The positive observations of each valid character following the last `k` of the first `N` characters of `char_seq`, and their sum. `k` must be below `N`.
+    /// Produces an Err if fewer than `N` characters are given
+    pub(crate) fn lower_order_observations(&self, char_seq: &[ValidChar], k: usize) -> Result<([f64; VALID_CHAR_COUNT], usize), String> {
+        if char_seq.len() < N {return Err("Not enough characters given to determine row".to_string())}
+        let lower_orders = self.lower_order_counts.get_or_init(|| LowerOrderCounts::from_store::<N, S>(&self.positive_char_samples));
+        let (rows, sums) = &lower_orders.orders[k];
+        let suffix = LowerOrderCounts::suffix_index(&char_seq[..N], k);
+        Ok((rows[suffix].map(|weight| weight as f64), sums[suffix]))
+    }
+    /// Tops `observations` up to `threshold` in the proportions of `shorter_estimate`, if fewer than `threshold` were observed
+    fn back_off(mut observations: [f64; VALID_CHAR_COUNT], sum: usize, threshold: usize, shorter_estimate: Option<[f64; VALID_CHAR_COUNT]>) -> ([f64; VALID_CHAR_COUNT], f64) {
+        match shorter_estimate {
+            Some(proportions) if sum < threshold => {
+                let missing = (threshold - sum) as f64;
+                for (weight, proportion) in observations.iter_mut().zip(proportions.iter()) {
+                    *weight += missing * proportion;
+                }
+                (observations, threshold as f64)
+            },
+            _ => (observations, sum as f64),
+        }
+    }
+}
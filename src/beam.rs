@@ -0,0 +1,78 @@
+use crate::{CharType, NameExperiments, ValidChar, WeightStore};
+
+/// A partly generated name within the beam
+#[derive(Clone)]
+struct BeamState<const N: usize> {
+    char_array: [ValidChar; N],
+    char_type_array: [CharType; N],
+    name_string: String,
+    log_probability: f64,
+}
+
+impl<const N: usize, S: WeightStore<{ValidChar::VARIANTCOUNT as usize}>> NameExperiments<N, S> {
+    /// The number of partly generated names kept per requested name while searching in `top_names`
+    pub const BEAM_WIDTH_PER_NAME: usize = 4;
+
+    /// Produces up to `count` of the most probable complete names under the model, paired with their probability and sorted most probable first. Unlike `build_random_name` this is
+    /// deterministic. Probabilities come from `generate_probability_distribution` with default settings, normalized at every character.
+    /// 
+    /// The search is a [beam search](https://en.wikipedia.org/wiki/Beam_search) that keeps `count * BEAM_WIDTH_PER_NAME` partly generated names at each length, so a name whose early
+    /// characters are unlikely can be missed. Names that have not terminated within `max_len` characters are dropped. Paths that only differ by syllable breaks produce the same name;
+    /// their probabilities are summed. Syllable breaks are not considered at the start of a name or directly after another syllable break. Ties are ordered alphabetically.
+    /// 
+    /// ## Parameters
+    /// * count: The number of names to produce
+    /// * max_len: The longest name considered
+    pub fn top_names(&self, count: usize, max_len: u8) -> Result<Vec<(String, f64)>, String> {
+        let beam_width = count.saturating_mul(Self::BEAM_WIDTH_PER_NAME);
+        let mut beam: Vec<BeamState<N>> = vec![BeamState {
+            char_array: [ValidChar::null; N],
+            char_type_array: [CharType::Null; N],
+            name_string: String::new(),
+            log_probability: 0.0,
+        }];
+        let mut completed: Vec<(String, f64)> = vec![];
+        while !beam.is_empty() {
+            let mut candidates: Vec<BeamState<N>> = vec![];
+            for state in beam.iter() {
+                let (probabilities, sum_of_probabilities, mut char_window) = self.distribution_with_sizes(
+                    &state.char_array, &state.char_type_array, state.name_string.len(), None, None, None, &self.name_sizes, None
+                )?;
+                if sum_of_probabilities <= 0.0 {continue;}
+                for (i, &p) in probabilities.iter().enumerate() {
+                    if p <= 0.0 {continue;}
+                    let next_char = ValidChar::ALLCHARS[i];
+                    let log_probability = state.log_probability + (p / sum_of_probabilities).ln();
+                    if next_char == ValidChar::null {
+                        let name = state.name_string.trim_end().to_string();
+                        match completed.iter_mut().find(|(n, _)| *n == name) {
+                            Some((_, total)) => *total += log_probability.exp(),
+                            None => completed.push((name, log_probability.exp())),
+                        }
+                        continue;
+                    }
+                    // Syllable breaks do not lengthen a name. Only allowing them between letters keeps every path finite
+                    if next_char == ValidChar::syllable_break && (state.name_string.is_empty() || state.char_array[N-1] == ValidChar::syllable_break) {continue;}
+                    let mut next = state.clone();
+                    if next_char != ValidChar::syllable_break {
+                        next.name_string.push(char::from(next_char));
+                    }
+                    if next.name_string.len() > max_len as usize {continue;}
+                    char_window[CharType::WINDOW-1] = next_char;
+                    next.char_array.rotate_left(1);
+                    next.char_array[N-1] = next_char;
+                    next.char_type_array.rotate_left(1);
+                    next.char_type_array[N-1] = CharType::try_from(&char_window)?;
+                    next.log_probability = log_probability;
+                    candidates.push(next);
+                }
+            }
+            candidates.sort_by(|a, b| b.log_probability.total_cmp(&a.log_probability).then_with(|| a.name_string.cmp(&b.name_string)));
+            candidates.truncate(beam_width);
+            beam = candidates;
+        }
+        completed.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        completed.truncate(count);
+        Ok(completed)
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{Name, NameExperiments, PaddingBias};
+
+/// Capacity of the `Name` used to convert text. Room for `NameExperiments::MAX_NAME_LENGTH` characters and a trailing `None`
+const NAME_CAPACITY: usize = u8::MAX as usize + 1;
+/// The longest label that fits within the label fields of `Name`
+const MAX_LABEL_LENGTH: usize = 16;
+
+/// Assembles a `NameExperiments` from several labeled bodies of names. Each name is read as a positive sample with `NameExperiments::read_positive_sample_for`,
+/// so that every label keeps its own character weights and `NameExperiments::build_random_name_for` produces names from the corpus of that label alone.
+///
+/// > Note: Every label holds a full set of weights, so each label costs as much memory as another experiment of the same `N`.
+///
+/// ```
+/// use random_person_name::ModelBuilder;
+///
+/// let mut builder: ModelBuilder<3> = ModelBuilder::new();
+/// builder.add_corpus("orc", &["Morgash", "Nargul", "Snarlgash"]).unwrap()
+///     .add_corpus("goblin", &["Snik", "Grib"]).unwrap();
+/// assert_eq!(builder.sample_counts(), vec![("orc", 3), ("goblin", 2)]);
+/// let experiments = builder.build();
+/// println!("Hello, {}!", experiments.build_random_name_for("goblin", Some(16)).unwrap());
+/// ```
+pub struct ModelBuilder<const N: usize> {
+    experiments: NameExperiments<N>,
+    sample_counts: Vec<(String, usize)>,
+}
+
+impl<const N: usize> Default for ModelBuilder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> ModelBuilder<N> {
+    /// Create a new builder around an empty `NameExperiments`. Panics under the same conditions as `NameExperiments::new`
+    pub fn new() -> Self {
+        ModelBuilder {
+            experiments: NameExperiments::new(),
+            sample_counts: vec![],
+        }
+    }
+    /// Reads every name in `names` as a positive sample labeled with `label`. Calling `add_corpus` again with the same label adds to that label.
+    ///
+    /// Leading and trailing whitespace is trimmed from the label and from each name. Produces an Err without reading any names if the label is empty or longer than 16 characters,
+    /// or if any name is empty or longer than `NameExperiments::MAX_NAME_LENGTH`.
+    pub fn add_corpus(&mut self, label: &str, names: &[&str]) -> Result<&mut Self, String> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err("A label must have at least one character".to_string());
+        }
+        if label.chars().count() > MAX_LABEL_LENGTH {
+            return Err(format!("The label {label} is longer than {MAX_LABEL_LENGTH} characters"));
+        }
+        let names: Vec<&str> = names.iter().map(|n| n.trim()).collect();
+        if let Some(name) = names.iter().find(|n| n.is_empty() || n.len() > NameExperiments::<N>::MAX_NAME_LENGTH) {
+            return Err(format!("The name \"{name}\" in {label} is empty or longer than {} characters", NameExperiments::<N>::MAX_NAME_LENGTH));
+        }
+        for &text in names.iter() {
+            let name: Name<NAME_CAPACITY> = Name::new(text, "", PaddingBias::Left, Some(label), None, None, None);
+            self.experiments.read_positive_sample_for(&name.text, label)?;
+        }
+        match self.sample_counts.iter_mut().find(|(l, _)| l == label) {
+            Some((_, count)) => *count += names.len(),
+            None => self.sample_counts.push((label.to_string(), names.len())),
+        }
+        Ok(self)
+    }
+    /// The number of names read for each label, in the order the labels were first added
+    pub fn sample_counts(&self) -> Vec<(&str, usize)> {
+        self.sample_counts.iter().map(|(label, count)| (label.as_str(), *count)).collect()
+    }
+    /// Produces the assembled `NameExperiments`
+    pub fn build(self) -> NameExperiments<N> {
+        self.experiments
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{NameExperiments, ValidChar, WeightStore};
+
+impl<const N: usize, S: WeightStore<{ValidChar::VARIANTCOUNT as usize}>> NameExperiments<N, S> {
+    /// Records the positions of the capital letters of a positive sample. Syllable breaks are not counted as positions.
+    pub(crate) fn add_capitalization_template(&mut self, capitals: Vec<u8>) {
+        match self.capitalization_templates.iter_mut().find(|(template, _)| *template == capitals) {
+            Some((_, count)) => *count += 1,
+            None => self.capitalization_templates.push((capitals, 1)),
+        }
+    }
+    /// The capitalization patterns observed in positive samples, as the positions of capital letters paired with the number of samples that followed the pattern.
+    /// E.g. "McTavish" and "O'Brien" both follow the pattern `[0, 2]`. Patterns are listed in the order they were first observed.
+    pub fn capitalization_templates(&self) -> &[(Vec<u8>, usize)] {
+        &self.capitalization_templates
+    }
+    /// Capitalizes `name` with a pattern picked from `capitalization_templates`, in proportion to how often each pattern was observed. Capitals past the end of `name` are ignored.
+    /// `name` is produced unchanged if no positive samples have been read.
+    pub fn apply_capitalization_template(&self, name: &str) -> String {
+        let total: usize = self.capitalization_templates.iter().map(|(_, count)| count).sum();
+        if total == 0 {
+            return name.to_string();
+        }
+        let mut pick = self.with_rng(|rng| rng.usize(..total));
+        let (capitals, _) = self.capitalization_templates.iter().find(|(_, count)| {
+            if pick < *count {true} else {
+                pick -= count;
+                false
+            }
+        }).expect("The pick is always less than the total count");
+        name.chars().enumerate().flat_map(|(i, c)| {
+            let upper = u8::try_from(i).is_ok_and(|i| capitals.contains(&i));
+            let (upper_chars, lower_chars) = (c.to_uppercase(), c.to_lowercase());
+            upper_chars.filter(move |_| upper).chain(lower_chars.filter(move |_| !upper))
+        }).collect()
+    }
+    /// Behaves like `build_random_name`, and capitalizes the name with `apply_capitalization_template`.
+    /// This reproduces capitalization common to the names read (e.g. "McLeod" or "O'Brien") rather than simple title casing.
+    pub fn build_random_name_cased(&self, hard_stop: Option<u8>) -> Result<String,String> {
+        Ok(self.apply_capitalization_template(&self.build_random_name(hard_stop)?))
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{NameExperiments, SizeDistribution, ValidChar, WeightStore};
+
+/// Settings that shape how a `NameExperiments` generates names. Held by the experiment and applied to every generated name. See `NameExperiments::set_generation_config`.
+///
+/// The default settings leave generation unchanged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+// Settings missing from older serialized experiments take their defaults
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GenerationConfig {
+    /// A context is a dead end when its most probable continuing (non-null) character holds less than this share of the probability distribution.
+    /// On a dead end generation removes the last guessed character and guesses again from the context before it. Names naturally become dead ends as they approach
+    /// their expected length, so small values (e.g. `0.05`) are advised. Defaults to `0.0`, which never detects a dead end
+    pub backtrack_threshold: f64,
+    /// The most characters removed by backtracking while generating one name. Defaults to `0`, which disables backtracking
+    pub max_backtracks: usize,
+    /// Strips dashes, apostrophes and `ValidChar::TERMINATOR` from the end of a generated name, e.g. "grukthar-" becomes "grukthar". Only the end of a name is affected,
+    /// a name never starts with a separator. Defaults to `false`
+    pub trim_trailing_separators: bool,
+    /// How much negative samples matter, applied as an exponent to the factors derived from negative observations. `0.0` ignores negative samples entirely (a positive only model),
+    /// `1.0` applies them in full and values above `1.0` amplify them. Also applies to `NameExperiments::generate_probability_distribution`. Defaults to `1.0`
+    pub negative_influence: f64,
+    /// Restricts generated names to typical lengths. A pair of percentiles (between `0.0` and `1.0`, lower first) of the lengths of the names read, e.g. `(0.1, 0.9)`.
+    /// Names are never terminated before the lower percentile length and always terminate at the upper percentile length. Defaults to `None`, which applies no restriction
+    pub length_percentile_range: Option<(f64, f64)>,
+    /// The fewest characters in a generated name. The null (terminating) character is not picked until a name reaches this length, unless nothing else can be picked.
+    /// `hard_stop` still applies, and when combined with `length_percentile_range` the longer of the two minimums is used. Defaults to `None`, which applies no minimum
+    pub min_length: Option<u8>,
+    /// The lowest share of a probability distribution a character can hold and still be picked. Characters below it are given a probability of exactly `0.0`,
+    /// which removes the long tail of improbable characters left by easing. Applied after every other factor, including `square_probabilities`, but never to the most probable character.
+    /// Also applies to `NameExperiments::generate_probability_distribution`. Defaults to `0.0`, which leaves every character possible
+    pub epsilon: f64,
+    /// Smooth control over how adventurous picks are. Before a character is picked each probability is raised to the power of `1.0 / temperature`. Values near `0.0` approach
+    /// always picking the most probable character, while values above `1.0` flatten the distribution for wilder names. Only applies to picking a character (e.g. `guess_next_char` and
+    /// `build_random_name`), not to `NameExperiments::generate_probability_distribution`, `NameExperiments::score_name` or `NameExperiments::top_names`.
+    /// Must be above `0.0`. Defaults to `1.0`, which leaves picks unchanged
+    pub temperature: f64,
+    /// Katz-style back-off for rarely observed contexts. A context followed by fewer than this many positive observations has its observations topped up to this many,
+    /// in the proportions estimated for the context one character shorter, which is itself backed off in the same way down to how often each character was observed at all.
+    /// Observations of the full context are kept, so a rare context still favours what followed it, while an unseen context is estimated from its shorter contexts rather than easing alone.
+    /// Only positive observations are backed off. Also applies to `NameExperiments::generate_probability_distribution`. Defaults to `0`, which never backs off
+    pub backoff_threshold: usize,
+}
+
+impl Default for GenerationConfig {
+    fn default() -> Self {
+        GenerationConfig {
+            backtrack_threshold: 0.0,
+            max_backtracks: 0,
+            trim_trailing_separators: false,
+            negative_influence: 1.0,
+            length_percentile_range: None,
+            min_length: None,
+            epsilon: 0.0,
+            temperature: 1.0,
+            backoff_threshold: 0,
+        }
+    }
+}
+
+impl GenerationConfig {
+    /// Raises every probability to the power of `1.0 / temperature`, relative to the most probable so that a low temperature can not underflow every entry.
+    /// Produces the new sum of probabilities. Produces an Err if `temperature` is not above `0.0`.
+    pub(crate) fn apply_temperature(&self, probabilities: &mut [f64], sum_of_probabilities: f64) -> Result<f64, String> {
+        let temperature = self.temperature;
+        if temperature.is_nan() || temperature <= 0.0 {
+            return Err(format!("temperature must be above 0.0. Found {temperature}"));
+        }
+        let max_probability = probabilities.iter().copied().fold(0.0, f64::max);
+        if temperature == 1.0 || max_probability <= 0.0 || !max_probability.is_finite() {
+            return Ok(sum_of_probabilities);
+        }
+        for p in probabilities.iter_mut() {
+            *p = (*p / max_probability).powf(1.0 / temperature);
+        }
+        Ok(probabilities.iter().sum())
+    }
+    /// The `name_sizes` histogram with every name shorter than `min_length` counted at `min_length`, so that names are expected to continue until they reach it.
+    /// Produces `None` if no minimum is set.
+    pub(crate) fn sizes_with_min_length(&self, name_sizes: &SizeDistribution) -> Option<SizeDistribution> {
+        let min_length = usize::from(self.min_length.filter(|&length| length > 0)?);
+        let (mut histogram, total) = name_sizes.clone();
+        if histogram.len() <= min_length {
+            histogram.resize(min_length + 1, 0);
+        }
+        let shorter: usize = histogram[..min_length].iter().sum();
+        histogram[..min_length].fill(0);
+        histogram[min_length] += shorter;
+        Some((histogram, total))
+    }
+    /// The shortest and longest name length allowed by `length_percentile_range`, read from the `name_sizes` histogram. A length is at a percentile when the names
+    /// no longer than it make up at least that share of the names read. Produces `None` if no range is set or if no names have been read.
+    pub(crate) fn length_bounds(&self, name_sizes: &SizeDistribution) -> Result<Option<(usize, usize)>, String> {
+        let Some((lower, upper)) = self.length_percentile_range else {return Ok(None)};
+        if !(0.0..=1.0).contains(&lower) || !(0.0..=1.0).contains(&upper) || lower > upper {
+            return Err(format!("length_percentile_range must hold two percentiles between 0.0 and 1.0 with the lower first. Found ({lower}, {upper})"));
+        }
+        let (histogram, total) = name_sizes;
+        if *total == 0 {return Ok(None);}
+        let percentile_length = |percentile: f64| -> usize {
+            let mut cumulative = 0usize;
+            histogram.iter().position(|&count| {
+                cumulative += count;
+                count > 0 && cumulative as f64 >= percentile * *total as f64
+            }).unwrap_or(histogram.len() - 1)
+        };
+        Ok(Some((percentile_length(lower), percentile_length(upper))))
+    }
+}
+
+impl<const N: usize, S: WeightStore<{ValidChar::VARIANTCOUNT as usize}>> NameExperiments<N, S> {
+    /// The settings applied while generating names
+    pub fn generation_config(&self) -> &GenerationConfig {
+        &self.generation_config
+    }
+    /// Replaces the settings applied while generating names, including names generated for a label by `build_random_name_for`
+    pub fn set_generation_config(&mut self, config: GenerationConfig) {
+        self.generation_config = config;
+        for labeled in self.labeled_experiments.values_mut() {
+            labeled.set_generation_config(config);
+        }
+    }
+}
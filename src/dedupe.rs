@@ -0,0 +1,48 @@
+use crate::{NameExperiments, TestType, ValidChar, WeightStore};
+
+/// Levenshtein distance between two character sequences
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+    for (i, &a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+impl<const N: usize, S: WeightStore<{ValidChar::VARIANTCOUNT as usize}>> NameExperiments<N, S> {
+    /// Reads names as positive samples while collapsing near-duplicates (e.g. "Grukthar" and "Grukthor"), which would otherwise over-reinforce a pattern. Returns the number of names that were collapsed.
+    /// 
+    /// Names are clustered in order. A name within `max_edit_distance` ([Levenshtein distance](https://en.wikipedia.org/wiki/Levenshtein_distance), ignoring case) of a name that was already read
+    /// is collapsed into it and not read. Otherwise it is read and becomes the representative of a new cluster. A `max_edit_distance` of `0` only collapses exact duplicates.
+    /// 
+    /// Leading and trailing whitespace is trimmed. Blank names and names longer than `MAX_NAME_LENGTH` characters are skipped and are not counted as collapsed.
+    /// > Note: Every name is compared against every representative. Expect this to be slow for very large batches.
+    pub fn train_positive_deduped_fuzzy(&mut self, names: &[&str], max_edit_distance: usize) -> Result<usize, String> {
+        let mut representatives: Vec<Vec<char>> = vec![];
+        let mut collapsed = 0usize;
+        let mut text: Vec<Option<char>> = Vec::with_capacity(Self::MAX_NAME_LENGTH + 1);
+        for name in names.iter() {
+            let name = name.trim();
+            if name.is_empty() || name.chars().count() > Self::MAX_NAME_LENGTH {continue;}
+            let folded: Vec<char> = name.chars().flat_map(char::to_lowercase).collect();
+            // The length difference is a lower bound of the edit distance. Skip the full computation when it is already too far
+            if representatives.iter().any(|r| r.len().abs_diff(folded.len()) <= max_edit_distance && edit_distance(r, &folded) <= max_edit_distance) {
+                collapsed += 1;
+                continue;
+            }
+            text.clear();
+            text.extend(name.chars().map(Some));
+            text.push(None);
+            self.read_sample(&text, TestType::Pos, None)?;
+            representatives.push(folded);
+        }
+        Ok(collapsed)
+    }
+}
+
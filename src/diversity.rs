@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+/// A summary of how varied a batch of generated names is. Produced by `NameExperiments::sample_diversity`.
+/// 
+/// A low `type_token_ratio` or a long list of `most_repeated` names usually indicates that the probability distribution is too sharp (e.g. `square_probabilities`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiversityReport {
+    /// The number of names that were generated
+    pub total_names: usize,
+    /// The number of distinct names within the generated names
+    pub unique_names: usize,
+    /// `unique_names / total_names`. A value of `1.0` means no name was repeated. Defaults to `0.0` when no names were generated
+    pub type_token_ratio: f64,
+    /// Names that were generated more than once, paired with the number of times they were generated. Sorted most frequent first and limited to `DiversityReport::MAX_REPEATED` entries
+    pub most_repeated: Vec<(String, usize)>,
+}
+
+impl DiversityReport {
+    /// The maximum number of entries kept in `most_repeated`
+    pub const MAX_REPEATED: usize = 10;
+
+    pub(crate) fn from_names(names: &[String]) -> Self {
+        let mut tally: HashMap<&str, usize> = HashMap::new();
+        for n in names.iter() {
+            *tally.entry(n.as_str()).or_insert(0) += 1;
+        }
+        let unique_names = tally.len();
+        let mut most_repeated: Vec<(String, usize)> = tally.into_iter()
+            .filter(|&(_, count)| count > 1)
+            .map(|(name, count)| (name.to_string(), count))
+            .collect();
+        // Ties are broken alphabetically so that the report is stable for a given set of names
+        most_repeated.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        most_repeated.truncate(Self::MAX_REPEATED);
+        DiversityReport {
+            total_names: names.len(),
+            unique_names,
+            type_token_ratio: if names.is_empty() {0.0} else {unique_names as f64 / names.len() as f64},
+            most_repeated,
+        }
+    }
+}
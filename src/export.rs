@@ -0,0 +1,173 @@
+use std::fmt::Write;
+
+use crate::ngramweights::NGramWeights;
+use crate::{CharType, NameExperiments, ValidChar};
+
+/// Appends a weights matrix to `bytes`: the number of rows, then every row of `V` weights, then the sum of every row
+fn write_weights<const N: usize, const V: usize>(bytes: &mut Vec<u8>, weights: &NGramWeights<N, V>) {
+    bytes.extend_from_slice(&(weights.weights.len() as u64).to_le_bytes());
+    for row in weights.weights.iter() {
+        bytes.extend_from_slice(row);
+    }
+    for &sum in weights.sum.iter() {
+        bytes.extend_from_slice(&(sum as u64).to_le_bytes());
+    }
+}
+
+/// Reads from the front of a byte slice, producing an Err rather than panicking when the bytes run out
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl ByteReader<'_> {
+    fn take(&mut self, count: usize) -> Result<&[u8], String> {
+        if self.bytes.len() < count {
+            return Err(format!("Unexpected end of weights. Expected {count} more bytes, found {}", self.bytes.len()));
+        }
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(taken)
+    }
+    fn read_u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().expect("Exactly 4 bytes were taken")))
+    }
+    fn read_usize(&mut self) -> Result<usize, String> {
+        let value = u64::from_le_bytes(self.take(8)?.try_into().expect("Exactly 8 bytes were taken"));
+        usize::try_from(value).map_err(|_| format!("{value} does not fit within a usize"))
+    }
+    fn read_weights<const N: usize, const V: usize>(&mut self) -> Result<NGramWeights<N, V>, String> {
+        let rows = self.read_usize()?;
+        let expected_rows = V.checked_pow(N as u32).ok_or(format!("Number of {N} ngrams picked will result in overflow of the weights"))?;
+        if rows != expected_rows {
+            return Err(format!("Expected {expected_rows} rows of {V} weights. Found {rows} rows"));
+        }
+        let weights = (0..rows).map(|_| Ok(self.take(V)?.try_into().expect("Exactly V bytes were taken"))).collect::<Result<Vec<[u8; V]>, String>>()?;
+        let sum = (0..rows).map(|_| self.read_usize()).collect::<Result<Vec<usize>, String>>()?;
+        Ok(NGramWeights { weights, sum })
+    }
+}
+
+/// Renders a character sequence as text. Null characters are rendered as `ValidChar::TERMINATOR` so that contexts at the start of a name remain readable.
+fn context_label(chars: &[ValidChar]) -> String {
+    chars.iter().map(|&ch| if ch == ValidChar::null {ValidChar::TERMINATOR} else {char::from(ch)}).collect()
+}
+
+impl<const N: usize> NameExperiments<N> {
+    /// Produces a [Graphviz DOT](https://graphviz.org/doc/info/lang.html) representation of the character transitions that have been observed in positive samples.
+    /// 
+    /// Every context (sequence of N characters) with at least one positive observation becomes a node. An edge is drawn to the context that follows when a character is picked,
+    /// labelled with that character and its probability. Probabilities combine positive and negative observations with the default easing and are normalized per context.
+    /// Picking the null character terminates a name and is drawn as an edge to a shared `<end>` node. Null characters within a context are rendered as `_`.
+    /// 
+    /// A context is left without edges if its probabilities can not be computed (e.g. its negative weights are inconsistent) or do not sum to a positive finite number
+    /// (e.g. negative observations outweigh every positive one), so that no edge is labelled `NaN`.
+    /// 
+    /// ## Parameters
+    /// * min_prob: Edges with a probability lower than this value are omitted
+    pub fn to_dot(&self, min_prob: f64) -> String {
+        let mut dot = String::from("digraph NameExperiments {\n");
+        for (index, &sum) in self.positive_char_samples.sum.iter().enumerate() {
+            if sum == 0 {continue;}
+            let mut context = [ValidChar::null; N];
+            for (ch, v) in context.iter_mut().zip(self.positive_char_samples.get_row_sequence(index)) {
+                *ch = ValidChar::ALLCHARS[v];
+            }
+            let Ok(probabilities) = self.combined_char_probabilities(&context, &[1.0; ValidChar::VARIANTCOUNT as usize], 1.0, None) else {continue};
+            let total: f64 = probabilities.iter().sum();
+            if total <= 0.0 || !total.is_finite() {continue;}
+            let from = context_label(&context);
+            for (i, p) in probabilities.iter().enumerate() {
+                let p = p / total;
+                if p < min_prob {continue;}
+                let next_char = ValidChar::ALLCHARS[i];
+                let (to, label) = if next_char == ValidChar::null {
+                    ("<end>".to_string(), "_".to_string())
+                } else {
+                    let mut next_context = context;
+                    next_context.rotate_left(1);
+                    next_context[N-1] = next_char;
+                    (context_label(&next_context), char::from(next_char).to_string())
+                };
+                let _ = writeln!(dot, "    \"{from}\" -> \"{to}\" [label=\"{label} {p:.3}\"];");
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+    /// Produces the lengths of the names read as CSV, with a `length,count,probability` header row. `probability` is the share of names read with that length.
+    /// 
+    /// Every length from `0` up to the longest name read has a row, including lengths that were never read, so that the lengths form a continuous axis when plotted.
+    /// Syllable breaks do not count towards the length of a name.
+    pub fn length_histogram_csv(&self) -> String {
+        let (histogram, total) = &self.name_sizes;
+        let mut csv = String::from("length,count,probability\n");
+        for (length, &count) in histogram.iter().enumerate() {
+            let probability = if *total == 0 {0.0} else {count as f64 / *total as f64};
+            let _ = writeln!(csv, "{length},{count},{probability}");
+        }
+        csv
+    }
+    /// Writes the weights and the length histogram of the experiment as a compact byte blob that can be read back with `import_weights`, e.g. to ship a pre-trained model.
+    /// Unlike the `serde` feature, no dependencies are needed.
+    /// 
+    /// The layout is a header of `N`, `ValidChar::VARIANTCOUNT` and `CharType::VARIANTCOUNT` (each a little endian `u32`), followed by the positive character, negative character,
+    /// positive character type and negative character type weights. Each holds its number of rows, every row of `u8` weights and then the sum of every row.
+    /// The length histogram follows as its number of entries, every entry and the number of names it was built from. Every count and sum is written as a little endian `u64`.
+    /// 
+    /// Labels, sample counts, capitalization templates and the `GenerationConfig` are not written.
+    pub fn export_weights(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = vec![];
+        bytes.extend_from_slice(&(N as u32).to_le_bytes());
+        bytes.extend_from_slice(&(ValidChar::VARIANTCOUNT as u32).to_le_bytes());
+        bytes.extend_from_slice(&(CharType::VARIANTCOUNT as u32).to_le_bytes());
+        write_weights(&mut bytes, &self.positive_char_samples);
+        write_weights(&mut bytes, &self.negative_char_samples);
+        write_weights(&mut bytes, &self.positive_char_type_samples);
+        write_weights(&mut bytes, &self.negative_char_type_samples);
+        bytes.extend_from_slice(&(self.name_sizes.0.len() as u64).to_le_bytes());
+        for &count in self.name_sizes.0.iter() {
+            bytes.extend_from_slice(&(count as u64).to_le_bytes());
+        }
+        bytes.extend_from_slice(&(self.name_sizes.1 as u64).to_le_bytes());
+        bytes
+    }
+    /// Reads an experiment from bytes written by `export_weights`.
+    /// 
+    /// Produces a descriptive Err if the header was written for another `N` or another set of characters or character types, or if the bytes are truncated or malformed.
+    pub fn import_weights(bytes: &[u8]) -> Result<Self, String> {
+        if N < 2 {
+            return Err("N must be at least 2".to_string());
+        }
+        let mut reader = ByteReader { bytes };
+        for (name, expected) in [("N", N), ("ValidChar::VARIANTCOUNT", ValidChar::VARIANTCOUNT as usize), ("CharType::VARIANTCOUNT", CharType::VARIANTCOUNT)] {
+            let found = reader.read_u32()?;
+            if found as usize != expected {
+                return Err(format!("The weights were exported with {name}={found}, but {name}={expected} is expected"));
+            }
+        }
+        // The weights are read before the experiment is built, so that default weights are never allocated only to be replaced
+        let positive_char_samples = reader.read_weights()?;
+        let negative_char_samples = reader.read_weights()?;
+        let positive_char_type_samples = reader.read_weights()?;
+        let negative_char_type_samples = reader.read_weights()?;
+        let entries = reader.read_usize()?;
+        if entries == 0 {
+            return Err("A length histogram must have at least one entry".to_string());
+        }
+        // Every entry takes 8 bytes. Checked before allocating so that a corrupt count can not request a huge allocation
+        if entries > reader.bytes.len() / 8 {
+            return Err(format!("Unexpected end of weights. The length histogram claims {entries} entries"));
+        }
+        let histogram = (0..entries).map(|_| reader.read_usize()).collect::<Result<Vec<usize>, String>>()?;
+        let total = reader.read_usize()?;
+        if histogram.iter().sum::<usize>() != total {
+            return Err(format!("The length histogram counts {} names but records a total of {total}", histogram.iter().sum::<usize>()));
+        }
+        if !reader.bytes.is_empty() {
+            return Err(format!("Found {} unexpected bytes after the weights", reader.bytes.len()));
+        }
+        let mut experiments = Self::from_weights(positive_char_samples, negative_char_samples, positive_char_type_samples, negative_char_type_samples)?;
+        experiments.name_sizes = (histogram, total);
+        Ok(experiments)
+    }
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+
+use crate::NameExperiments;
+
+/// An issue with a `NameExperiments` that is likely to result in poor generated names. Produced by `NameExperiments::health_check`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Warning {
+    /// No positive samples have been read. Names can not be generated
+    NoPositiveSamples,
+    /// Fewer distinct character sequences have been observed in positive samples than `Warning::MIN_CONTEXTS`. Generation is likely to repeat the names that were read
+    FewContexts {
+        /// The number of distinct character sequences observed
+        observed: usize,
+    },
+    /// A single name length accounts for more than `Warning::MAX_LENGTH_SHARE` of the names read. Generated names will mostly be of this length
+    DominantLength {
+        /// The dominant name length
+        length: usize,
+        /// The share of names read with this length, between `0.0` and `1.0`
+        share: f64,
+    },
+    /// Negative observations outnumber positive observations in more than `Warning::MAX_NEGATIVE_SHARE` of the observed character sequences
+    NegativeOverwhelms {
+        /// The share of observed character sequences where negative observations outnumber positive observations, between `0.0` and `1.0`
+        share: f64,
+    },
+}
+
+impl Warning {
+    /// The fewest distinct character sequences that are expected from positive samples
+    pub const MIN_CONTEXTS: usize = 30;
+    /// The largest share of names that a single length is expected to account for
+    pub const MAX_LENGTH_SHARE: f64 = 0.5;
+    /// The largest share of character sequences that negative observations are expected to dominate
+    pub const MAX_NEGATIVE_SHARE: f64 = 0.25;
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::NoPositiveSamples => write!(f, "no positive samples have been read"),
+            Warning::FewContexts { observed } => write!(f, "fewer than {} distinct contexts observed ({observed})", Warning::MIN_CONTEXTS),
+            Warning::DominantLength { length, share } => write!(f, "length histogram dominated by names of length {length} ({:.0}%)", share * 100.0),
+            Warning::NegativeOverwhelms { share } => write!(f, "negative samples overwhelm positive samples for {:.0}% of contexts", share * 100.0),
+        }
+    }
+}
+
+impl<const N: usize> NameExperiments<N> {
+    /// Looks for signs that too little (or unbalanced) data has been read, which tends to collapse generation into a few repeated or always short names.
+    /// Produces an empty Vec if no issues are found. See `Warning` for the thresholds applied.
+    pub fn health_check(&self) -> Vec<Warning> {
+        let mut warnings = vec![];
+        if self.total_positive_samples() == 0 {
+            warnings.push(Warning::NoPositiveSamples);
+        }
+        let observed = self.positive_char_samples.sum.iter().filter(|&&sum| sum > 0).count();
+        if observed < Warning::MIN_CONTEXTS {
+            warnings.push(Warning::FewContexts { observed });
+        }
+        if self.name_sizes.1 > 0 {
+            let (length, &count) = self.name_sizes.0.iter().enumerate()
+                .max_by_key(|&(_, &count)| count)
+                .expect("The length histogram always has at least one entry");
+            let share = count as f64 / self.name_sizes.1 as f64;
+            if share > Warning::MAX_LENGTH_SHARE {
+                warnings.push(Warning::DominantLength { length, share });
+            }
+        }
+        let (contexts, overwhelmed) = self.positive_char_samples.sum.iter().zip(self.negative_char_samples.sum.iter())
+            .filter(|&(&pos, &neg)| pos > 0 || neg > 0)
+            .fold((0usize, 0usize), |(contexts, overwhelmed), (&pos, &neg)| (contexts + 1, overwhelmed + usize::from(neg > pos)));
+        if contexts > 0 {
+            let share = overwhelmed as f64 / contexts as f64;
+            if share > Warning::MAX_NEGATIVE_SHARE {
+                warnings.push(Warning::NegativeOverwhelms { share });
+            }
+        }
+        warnings
+    }
+}
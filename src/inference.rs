@@ -0,0 +1,158 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{CharType, Name, NameExperiments, RandomSource, SamplingStrategy, ValidChar};
+
+/// A read-only view of a trained `NameExperiments` that only exposes generation. Created with `NameExperiments::as_inference`.
+/// 
+/// The view borrows the weights of the experiment rather than copying them, and is itself cheap to copy. While a view exists the experiment can not be trained further.
+#[derive(Clone, Copy)]
+pub struct InferenceModel<'a, const N: usize> {
+    pub(crate) experiments: &'a NameExperiments<N>,
+}
+
+impl<const N: usize> NameExperiments<N> {
+    /// Borrows the experiment as an `InferenceModel` that can generate names but can not read further samples.
+    pub fn as_inference(&self) -> InferenceModel<'_, N> {
+        InferenceModel { experiments: self }
+    }
+}
+
+impl<const N: usize> InferenceModel<'_, N> {
+    /// See `NameExperiments::generate_probability_distribution`
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_probability_distribution(
+        &self,
+        char_seq: &[ValidChar],
+        char_type_seq: &[CharType],
+        character_count: usize,
+        pos_easing_scale: Option<f64>,
+        neg_easing_scale: Option<f64>,
+        square_probabilities: Option<bool>,
+        exclude_null: Option<bool>
+    ) -> Result<([f64; ValidChar::VARIANTCOUNT as usize], f64, [ValidChar; CharType::WINDOW]), String> {
+        self.experiments.generate_probability_distribution(char_seq, char_type_seq, character_count, pos_easing_scale, neg_easing_scale, square_probabilities, exclude_null)
+    }
+    /// See `NameExperiments::generate_probability_distribution_with_easing`
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_probability_distribution_with_easing(
+        &self,
+        char_seq: &[ValidChar],
+        char_type_seq: &[CharType],
+        character_count: usize,
+        pos_easing: &[f64; ValidChar::VARIANTCOUNT as usize],
+        neg_easing_scale: Option<f64>,
+        square_probabilities: Option<bool>,
+        exclude_null: Option<bool>
+    ) -> Result<([f64; ValidChar::VARIANTCOUNT as usize], f64, [ValidChar; CharType::WINDOW]), String> {
+        self.experiments.generate_probability_distribution_with_easing(char_seq, char_type_seq, character_count, pos_easing, neg_easing_scale, square_probabilities, exclude_null)
+    }
+    /// See `NameExperiments::generate_probability_distribution_interpolated`
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_probability_distribution_interpolated(
+        &self,
+        char_seq: &[ValidChar],
+        char_type_seq: &[CharType],
+        character_count: usize,
+        pos_easing_scale: Option<f64>,
+        neg_easing_scale: Option<f64>,
+        square_probabilities: Option<bool>,
+        exclude_null: Option<bool>,
+        lambdas: &[f64]
+    ) -> Result<([f64; ValidChar::VARIANTCOUNT as usize], f64, [ValidChar; CharType::WINDOW]), String> {
+        self.experiments.generate_probability_distribution_interpolated(char_seq, char_type_seq, character_count, pos_easing_scale, neg_easing_scale, square_probabilities, exclude_null, lambdas)
+    }
+    /// See `NameExperiments::distribution_as_map`
+    #[allow(clippy::too_many_arguments)]
+    pub fn distribution_as_map(
+        &self,
+        char_seq: &[ValidChar],
+        char_type_seq: &[CharType],
+        character_count: usize,
+        pos_easing_scale: Option<f64>,
+        neg_easing_scale: Option<f64>,
+        square_probabilities: Option<bool>,
+        exclude_null: Option<bool>
+    ) -> Result<BTreeMap<char, f64>, String> {
+        self.experiments.distribution_as_map(char_seq, char_type_seq, character_count, pos_easing_scale, neg_easing_scale, square_probabilities, exclude_null)
+    }
+    /// See `NameExperiments::guess_next_char`
+    pub fn guess_next_char(&self, char_seq: &[ValidChar], char_type_seq: &[CharType], current_char_count: usize) -> Result<(ValidChar, CharType), String> {
+        self.experiments.guess_next_char(char_seq, char_type_seq, current_char_count)
+    }
+    /// See `NameExperiments::guess_next_char_with_strategy`
+    pub fn guess_next_char_with_strategy(&self, char_seq: &[ValidChar], char_type_seq: &[CharType], current_char_count: usize, strategy: SamplingStrategy) -> Result<(ValidChar, CharType), String> {
+        self.experiments.guess_next_char_with_strategy(char_seq, char_type_seq, current_char_count, strategy)
+    }
+    /// See `NameExperiments::guess_next_char_seeded`
+    pub fn guess_next_char_seeded(&self, char_seq: &[ValidChar], char_type_seq: &[CharType], current_char_count: usize, rng: &mut impl RandomSource) -> Result<(ValidChar, CharType), String> {
+        self.experiments.guess_next_char_seeded(char_seq, char_type_seq, current_char_count, rng)
+    }
+    /// See `NameExperiments::top_k_next`
+    pub fn top_k_next(&self, char_seq: &[ValidChar], char_type_seq: &[CharType], current_char_count: usize, k: usize) -> Result<Vec<(ValidChar, f64)>, String> {
+        self.experiments.top_k_next(char_seq, char_type_seq, current_char_count, k)
+    }
+    /// See `NameExperiments::build_random_name`
+    pub fn build_random_name(&self, hard_stop: Option<u8>) -> Result<String, String> {
+        self.experiments.build_random_name(hard_stop)
+    }
+    /// See `NameExperiments::build_random_names`
+    pub fn build_random_names(&self, count: usize, hard_stop: Option<u8>) -> Result<Vec<String>, String> {
+        self.experiments.build_random_names(count, hard_stop)
+    }
+    /// See `NameExperiments::names`
+    pub fn names(&self, hard_stop: Option<u8>) -> impl Iterator<Item = Result<String, String>> + '_ {
+        self.experiments.names(hard_stop)
+    }
+    /// See `NameExperiments::build_random_name_seeded`
+    pub fn build_random_name_seeded(&self, hard_stop: Option<u8>, rng: &mut impl RandomSource) -> Result<String, String> {
+        self.experiments.build_random_name_seeded(hard_stop, rng)
+    }
+    /// See `NameExperiments::build_random_name_with_strategy`
+    pub fn build_random_name_with_strategy(&self, hard_stop: Option<u8>, strategy: SamplingStrategy) -> Result<String, String> {
+        self.experiments.build_random_name_with_strategy(hard_stop, strategy)
+    }
+    /// See `NameExperiments::build_random_name_labeled`
+    pub fn build_random_name_labeled<const L: usize, const M: usize>(&self, hard_stop: Option<u8>, labels: &Name<L>) -> Result<Name<M>, String> {
+        self.experiments.build_random_name_labeled(hard_stop, labels)
+    }
+    /// See `NameExperiments::build_random_name_for`
+    pub fn build_random_name_for(&self, label: &str, hard_stop: Option<u8>) -> Result<String, String> {
+        self.experiments.build_random_name_for(label, hard_stop)
+    }
+    /// See `NameExperiments::build_random_name_filtered`
+    pub fn build_random_name_filtered(&self, blocklist: &HashSet<String>, blocked_substrings: &[&str], hard_stop: Option<u8>, max_attempts: usize) -> Result<String, String> {
+        self.experiments.build_random_name_filtered(blocklist, blocked_substrings, hard_stop, max_attempts)
+    }
+    /// See `NameExperiments::build_random_name_starting_with`
+    pub fn build_random_name_starting_with(&self, start_filter: Option<&[ValidChar]>, hard_stop: Option<u8>) -> Result<String, String> {
+        self.experiments.build_random_name_starting_with(start_filter, hard_stop)
+    }
+    /// See `NameExperiments::build_novel_name`
+    pub fn build_novel_name(&self, seen: &HashSet<String>, max_attempts: u32, hard_stop: Option<u8>) -> Result<String, String> {
+        self.experiments.build_novel_name(seen, max_attempts, hard_stop)
+    }
+    /// See `NameExperiments::build_random_name_syllables`
+    pub fn build_random_name_syllables(&self, target: u8, hard_stop: Option<u8>) -> Result<String, String> {
+        self.experiments.build_random_name_syllables(target, hard_stop)
+    }
+    /// See `NameExperiments::build_random_name_cased`
+    pub fn build_random_name_cased(&self, hard_stop: Option<u8>) -> Result<String, String> {
+        self.experiments.build_random_name_cased(hard_stop)
+    }
+    /// See `NameExperiments::score_name`
+    pub fn score_name(&self, text: &[Option<char>]) -> Result<f64, String> {
+        self.experiments.score_name(text)
+    }
+    /// See `NameExperiments::perplexity`
+    pub fn perplexity(&self, names: &[&[Option<char>]]) -> Result<f64, String> {
+        self.experiments.perplexity(names)
+    }
+    /// See `NameExperiments::top_names`
+    pub fn top_names(&self, count: usize, max_len: u8) -> Result<Vec<(String, f64)>, String> {
+        self.experiments.top_names(count, max_len)
+    }
+    /// See `NameExperiments::build_similar_name`
+    pub fn build_similar_name(&self, like: &str, hard_stop: Option<u8>) -> Result<String, String> {
+        self.experiments.build_similar_name(like, hard_stop)
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{CharType, NameExperiments, ValidChar, WeightStore};
+
+const VALID_CHAR_COUNT: usize = ValidChar::VARIANTCOUNT as usize;
+
+/// How far the interpolation weights may sum from `1.0`
+const LAMBDA_TOLERANCE: f64 = 1e-9;
+
+impl<const N: usize, S: WeightStore<{ValidChar::VARIANTCOUNT as usize}>> NameExperiments<N, S> {
+    /// Behaves like `generate_probability_distribution`, but the positive probability of each character is a linear interpolation of its estimates after contexts of every length,
+    /// from no context at all (how often each character was observed) up to the full context of `N` characters. Shorter contexts are observed far more often,
+    /// so blending them in sharpens the distributions of rarely observed contexts without more samples, e.g. for `N = 3` and a small corpus.
+    ///
+    /// Each estimate is eased as described in `generate_probability_distribution`, and the full context estimate is backed off if `GenerationConfig::backoff_threshold` is set.
+    /// Negative samples, character types and name lengths are then applied to the interpolated probability as usual.
+    ///
+    /// ## Parameters
+    /// * lambdas: `N + 1` weights for the estimates, ordered by the length of their context (`lambdas[0]` weights the estimate without context, `lambdas[N]` the full context).
+    ///   Produces an Err unless every weight is at least `0.0` and the weights sum to `1.0`. Passing `1.0` for the full context alone is equivalent to `generate_probability_distribution`
+    /// * every other parameter is described by `generate_probability_distribution`
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_probability_distribution_interpolated(
+        &self,
+        char_seq: &[ValidChar],
+        char_type_seq: &[CharType],
+        character_count: usize,
+        pos_easing_scale: Option<f64>,
+        neg_easing_scale: Option<f64>,
+        square_probabilities: Option<bool>,
+        exclude_null: Option<bool>,
+        lambdas: &[f64],
+    ) -> Result<([f64; VALID_CHAR_COUNT], f64, [ValidChar; CharType::WINDOW]), String> {
+        Self::check_lambdas(lambdas)?;
+        let (mut probabilities, mut sum_of_probabilities, char_window) = self.distribution_with_sizes(
+            char_seq, char_type_seq, character_count, Some(&[pos_easing_scale.unwrap_or(1.0); VALID_CHAR_COUNT]), neg_easing_scale, square_probabilities, &self.name_sizes, Some(lambdas)
+        )?;
+        if exclude_null.unwrap_or(false) {
+            probabilities[VALID_CHAR_COUNT - 1] = 0.0;
+            sum_of_probabilities = probabilities.iter().sum::<f64>();
+        }
+        Ok((probabilities, sum_of_probabilities, char_window))
+    }
+    /// Checks that `lambdas` holds `N + 1` weights of at least `0.0` that sum to `1.0`
+    fn check_lambdas(lambdas: &[f64]) -> Result<(), String> {
+        if lambdas.len() != N + 1 {
+            return Err(format!("Expected {} interpolation weights for N={N}, one for each context length from 0 to {N}. Found {}", N + 1, lambdas.len()));
+        }
+        if let Some(lambda) = lambdas.iter().find(|&&lambda| !(lambda >= 0.0 && lambda.is_finite())) {
+            return Err(format!("Interpolation weights must be at least 0.0. Found {lambda}"));
+        }
+        let sum: f64 = lambdas.iter().sum();
+        if (sum - 1.0).abs() > LAMBDA_TOLERANCE {
+            return Err(format!("Interpolation weights must sum to 1.0. Found a sum of {sum}"));
+        }
+        Ok(())
+    }
+    /// The eased positive probability of each valid character following `char_seq`. Interpolated over every context length if `lambdas` are given
+    pub(crate) fn positive_probabilities(&self, char_seq: &[ValidChar], pos_easing: &[f64; VALID_CHAR_COUNT], lambdas: Option<&[f64]>) -> Result<[f64; VALID_CHAR_COUNT], String> {
+        let pos_easing_sum = pos_easing.iter().sum::<f64>();
+        let eased = |observations: [f64; VALID_CHAR_COUNT], sum: f64| -> [f64; VALID_CHAR_COUNT] {
+            std::array::from_fn(|i| (observations[i] + pos_easing[i]) / (sum + pos_easing_sum))
+        };
+        let (observations, sum) = self.positive_observations(char_seq)?;
+        let full_context = eased(observations, sum);
+        let Some(lambdas) = lambdas else {return Ok(full_context)};
+        let mut probabilities = full_context.map(|p| p * lambdas[N]);
+        for (k, &lambda) in lambdas.iter().enumerate().take(N) {
+            if lambda == 0.0 {continue;}
+            let (observations, sum) = self.lower_order_observations(char_seq, k)?;
+            for (p, estimate) in probabilities.iter_mut().zip(eased(observations, sum as f64)) {
+                *p += lambda * estimate;
+            }
+        }
+        Ok(probabilities)
+    }
+}
@@ -10,14 +10,16 @@
 //! 1. Invoke a mutable instance of a `NameExperiment` N=2 or N=3 are reasonable starting points.
 //! 2. Utilize `Name` struct to handle raw &str of name text or perform manual conversion from `&str` to `&[Option<char>]`. Dedupe if desired.
 //! 3. Iterate through names and run `NameExperiment::read_positive_sample` on each.
-//! 4. Utilize `NameExperiment::build_random_name`. Apply external analysis to separate valid names from non names.
+//! 4. Utilize `NameExperiment::build_random_name`. Apply external analysis to separate valid names from non names. `NameExperiment::score_name` can rank names or filter them by a threshold.
 //! 5. Reinforce the weights within the `NameExperiment` by continuing to call `NameExperiment::read_positive_sample` and `NameExperiment::read_negative_sample` using valid and invalid names.
 //! 
 //! ## Examples
 //! ```
+//! use random_person_name::{Name, NameExperiments, PaddingBias};
+//!
 //! let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
-//! let orc_names: &[str] = &["Morgash", "Nargul", "Snarlgash"];
-//! let names = Name::new_from_batch(orc_names,
+//! let orc_names: &[&str] = &["Morgash", "Nargul", "Snarlgash"];
+//! let names: Vec<Name<16>> = Name::new_from_batch(orc_names,
 //!     "male",
 //!     PaddingBias::Left,
 //!     Some("Orc"),
@@ -26,7 +28,7 @@
 //!     None
 //! );
 //! for n in names.iter() {
-//!     let _ = name_guess_experiments.read_positive_sample(&n.text).unwrap();
+//!     name_guess_experiments.read_positive_sample(&n.text).unwrap();
 //! }
 //! let new_name = name_guess_experiments.build_random_name(Some(16)).unwrap();
 //! println!("Hello, {}!", new_name);
@@ -48,40 +50,105 @@
 //! 
 //! > Note: Again, `read_negative_sample` does not de-duplicate names.
 //! 
+//! ### Syllables
+//! Names may be annotated with syllable boundaries using `|` (e.g. `"gruk|thar"`). Boundaries are learned like any other character, so the model picks up where syllables tend to start and end.
+//! They do not count towards the length of a name and are never included in names produced by `NameExperiments::build_random_name`.
+//! 
+//! ### Compound names
+//! A space within a name (e.g. `"Iron maul"`) is learned as `ValidChar::space`, so compound names can be generated. Generated names never start with a space (or any other separator), never hold two spaces in a row
+//! and never end with a space.
+//! 
+//! ### Capitalization
+//! Reading is case insensitive, but the positions of capital letters in positive samples are remembered (e.g. "McTavish" and "O'Brien" capitalize positions 0 and 2).
+//! `NameExperiments::build_random_name_cased` applies one of these patterns, picked in proportion to how often it was read, to a generated name.
+//! 
+//! ### Saving a model
+//! With the `serde` feature enabled, `NameExperiments` implements `serde::Serialize` and `serde::Deserialize`, so a trained model can be written (e.g. to JSON or bincode) and read back
+//! to continue reinforcement later. Weights written for another `N`, or malformed weights, produce an error rather than a panic.
+//! 
+//! Without any feature, `NameExperiments::export_weights` writes the weights and length histogram as compact bytes, which `NameExperiments::import_weights` reads back.
+//! 
 //! ### Runtime Memory impact
 //! Under the hood, the weights of the samples are stored within Four total `Vec` that are size allocated when "new" is called. Two of the `Vec` instances are used to hold observations about character
 //! sequences and the count of an N+1 character observations in an array of length corresponding to the number of `ValidChar` variants.
 //! The other two `Vec` instances hold observation data about character type sequences and following character type encounters in an array of length corresponding to the number of `CharType` variants.
 //! 
 //! All observation is stored in u8 format to minimize the memory impact of the weights (see Intended Goal), but analysis of larger data sets with frequent occurences of the same ngram sets may prove this
-//! primitive too small.
-//! Given an `N`` number of preceding characters assuming that there are 29 valid characters and 10 character types
-//! the `NameExperiment` holds two `Vec` of capacity `29^N` and each array within the vec will be size 29 bytes. Meanwhile the two char_type sample weights will be `10^N` with arrays of size 10 bytes.
-//! In the case of `N=2` memory footprint is estimated to be 51 kB. In the case of `N=3` memory footprint is estimated to be 1.4 MB.
-//! > For reference: In a system that loads a corpus of names (of average length 8). 1.4 MB could hold around 22,400 names. But would be dependant on a user to provide the names.
+//! primitive too small. A wider counter can be chosen with the weight store, e.g. `NameExperiments<3, NGramWeights<3, 31, u16>>` counts every observation in a `u16`
+//! (at twice the memory), and the character type weights follow the same width.
+//! Given an `N`` number of preceding characters assuming that there are 31 valid characters and 13 character types
+//! the `NameExperiment` holds two `Vec` of capacity `31^N` and each array within the vec will be size 31 bytes. Meanwhile the two char_type sample weights will be `13^N` with arrays of size 13 bytes.
+//! In the case of `N=2` memory footprint is estimated to be 64 kB. In the case of `N=3` memory footprint is estimated to be 1.90 MB.
+//! With the `articulation` feature consonants are subdivided by place of articulation into 21 character types (see `CharType`), which raises the `N=3` estimate to 2.24 MB.
+//! > For reference: In a system that loads a corpus of names (of average length 8). 1.90 MB could hold around 30,000 names. But would be dependant on a user to provide the names.
 //! 
 //! ## TODO
-//! * Exports weights and import weights to facilitate storage and retrieval between reinforcement sessions.
 //! * Measure runtime memory impact and compare to estimated
 //! * Estimates provided in the runtime memory impact imply that names could be generated with significantly lower memory consumption if the system relies on lower dimensions of character
-//!  encoding (e.g. character type classifications) instead of using lengthier ngrams.
+//!   encoding (e.g. character type classifications) instead of using lengthier ngrams.
 //! 
 #![warn(missing_docs)]
 use std::vec;
-use fastrand::{f64 as rand_float};
-use ngramweights::NGramWeights;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+use fastrand::Rng;
+use std::io::BufRead;
 
 
 mod validchars;
 mod char_types;
 mod ngramweights;
 mod name;
+mod diversity;
+mod export;
+mod analysis;
+mod inference;
+mod sampling;
+mod builder;
+mod health;
+mod casing;
+mod config;
+mod dedupe;
+mod beam;
+mod unique;
+mod negatives;
+mod alphabet;
+mod scoring;
+mod backoff;
+mod interpolation;
+mod syllable;
+#[cfg(feature = "rand")]
+mod rand_distribution;
+#[cfg(feature = "serde")]
+mod serialization;
 #[cfg(test)]
 mod tests;
 
 pub use crate::name::{Name, PaddingBias};
 pub use crate::validchars::{ValidChar};
 pub use crate::char_types::{CharType};
+pub use crate::diversity::{DiversityReport};
+pub use crate::inference::{InferenceModel};
+pub use crate::sampling::{RandomSource, SamplingStrategy};
+pub use crate::analysis::{TransitionEstimate};
+pub use crate::builder::{ModelBuilder};
+pub use crate::health::{Warning};
+pub use crate::config::{GenerationConfig};
+pub use crate::unique::{UniqueNameStream};
+pub use crate::ngramweights::{Counter, NGramWeights, WeightStore};
+pub use crate::alphabet::{AlphabetWeights};
+pub use crate::syllable::{count_syllables};
+
+/// A histogram of name lengths paired with the number of names it was built from
+type SizeDistribution = (Vec<usize>, usize);
+
+/// The share of the names in `name_sizes` that are no longer than `character_count`, i.e. the names that would have ended by the time `character_count` characters were produced.
+/// Clamped between `0.0` and `1.0` in case the total of the histogram disagrees with its counts. Produces NaN if the histogram is built from no names.
+pub(crate) fn termination_probability(name_sizes: &SizeDistribution, character_count: usize) -> f64 {
+    let (histogram, total) = name_sizes;
+    let ended: usize = histogram.iter().take(character_count.saturating_add(1)).sum();
+    (ended as f64 / *total as f64).clamp(0.0, 1.0)
+}
 
 #[derive(Debug,Copy,Clone)]
 enum TestType {
@@ -96,43 +163,148 @@ enum TestType {
 /// 
 /// The number of characters that are include in a character sequence experiment also correlates to the experiment around character types. Some character sound types require analysis of 3 characters to be effective
 /// at correctly categorizing how a character influences phonetics in the word. E.g. 'Niche'
-pub struct NameExperiments<const N: usize> {
-    positive_char_samples: NGramWeights<N, {ValidChar::VARIANTCOUNT as usize}>,
-    negative_char_samples: NGramWeights<N, {ValidChar::VARIANTCOUNT as usize}>,
-    positive_char_type_samples: NGramWeights<N, {CharType::VARIANTCOUNT}>,
-    negative_char_type_samples: NGramWeights<N, {CharType::VARIANTCOUNT}>,
-    name_sizes: (Vec<usize>, usize)
+/// 
+/// `S` is the `WeightStore` holding the character weights. It defaults to a dense store of `u8` counts, and the character type weights are counted in the same `Counter` as `S`. Reading, configuration and the generation methods of the experiment itself
+/// (e.g. `build_random_name`) work with any store, while analysis, export and borrowed views (e.g. `InferenceModel`) are only available with the default store.
+pub struct NameExperiments<const N: usize, S: WeightStore<{ValidChar::VARIANTCOUNT as usize}> = NGramWeights<N, {ValidChar::VARIANTCOUNT as usize}>> {
+    positive_char_samples: S,
+    negative_char_samples: S,
+    positive_char_type_samples: NGramWeights<N, {CharType::VARIANTCOUNT}, S::Counter>,
+    negative_char_type_samples: NGramWeights<N, {CharType::VARIANTCOUNT}, S::Counter>,
+    name_sizes: SizeDistribution,
+    // Ordered so that anything iterating over labels is reproducible across runs and platforms
+    labeled_name_sizes: BTreeMap<String, SizeDistribution>,
+    // A full experiment for each label read through `read_positive_sample_for`
+    labeled_experiments: BTreeMap<String, NameExperiments<N, S>>,
+    positive_sample_count: usize,
+    negative_sample_count: usize,
+    rng: Mutex<Option<Rng>>,
+    capitalization_templates: Vec<(Vec<u8>, usize)>,
+    generation_config: GenerationConfig,
+    negative_penalties: Option<negatives::NegativePenalties<N>>,
+    // Derived from the positive character weights the first time back-off needs them, and discarded whenever a positive sample is read
+    lower_order_counts: OnceLock<backoff::LowerOrderCounts>,
+}
+
+impl<const N: usize, S: WeightStore<{ValidChar::VARIANTCOUNT as usize}>> Default for NameExperiments<N, S> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl<const N: usize> NameExperiments<N> {
+impl<const N: usize, S: WeightStore<{ValidChar::VARIANTCOUNT as usize}>> NameExperiments<N, S> {
+    /// The longest name (in characters) that is accepted while reading from a stream. Longer lines are skipped.
+    pub const MAX_NAME_LENGTH: usize = u8::MAX as usize;
+    /// The number of names generated by `expected_generated_length` to estimate the mean
+    pub const EXPECTED_LENGTH_SAMPLES: usize = 200;
     /// Create a new instance of a naming experiment. Ready to recieve names after created.
     /// Panics if generic parameter N < 2. Or if a choice of N will result in a u32 overflow
     /// Memory foot-print of the structure increases O(x^N)
     pub fn new() -> Self {
+        Self::try_new().unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Behaves like `new`, but produces an Err rather than panicking if generic parameter N < 2 or if a choice of N will result in an overflow.
+    /// Useful when N is chosen by a user of an application.
+    pub fn try_new() -> Result<Self, String> {
         if N < 2 {
-            panic!("N must be at least 2");
+            return Err("N must be at least 2".to_string());
         }
         if (ValidChar::VARIANTCOUNT as usize).checked_pow(N as u32).is_none() {
-            panic!("Number of {} ngrams picked will result in overflow",N);
+            return Err(format!("Number of {N} ngrams picked will result in overflow of the character weights"));
+        }
+        if CharType::VARIANTCOUNT.checked_pow(N as u32).is_none() {
+            return Err(format!("Number of {N} ngrams picked will result in overflow of the character type weights"));
         }
-        NameExperiments { 
-            positive_char_samples: NGramWeights::new(),
-            negative_char_samples: NGramWeights::new(),
-            positive_char_type_samples: NGramWeights::new(),
-            negative_char_type_samples: NGramWeights::new(),
+        Self::from_weights(S::try_new()?, S::try_new()?, NGramWeights::try_new()?, NGramWeights::try_new()?)
+    }
+    /// Create an experiment around weights that were already allocated, e.g. read from bytes or limited to an alphabet, so that no default store is allocated only to be replaced.
+    /// Everything else starts out as in `new`. Produces an Err if generic parameter N < 2
+    fn from_weights(
+        positive_char_samples: S,
+        negative_char_samples: S,
+        positive_char_type_samples: NGramWeights<N, {CharType::VARIANTCOUNT}, S::Counter>,
+        negative_char_type_samples: NGramWeights<N, {CharType::VARIANTCOUNT}, S::Counter>,
+    ) -> Result<Self, String> {
+        if N < 2 {
+            return Err("N must be at least 2".to_string());
+        }
+        Ok(NameExperiments {
+            positive_char_samples,
+            negative_char_samples,
+            positive_char_type_samples,
+            negative_char_type_samples,
             name_sizes: (vec![0], 0),
+            labeled_name_sizes: BTreeMap::new(),
+            labeled_experiments: BTreeMap::new(),
+            positive_sample_count: 0,
+            negative_sample_count: 0,
+            rng: Mutex::new(None),
+            capitalization_templates: vec![],
+            generation_config: GenerationConfig::default(),
+            negative_penalties: None,
+            lower_order_counts: OnceLock::new(),
+        })
+    }
+    /// Forgets everything that was read, so that the experiment can be trained again without allocating a new one. The character and character type weights are zeroed in place,
+    /// and the length histograms, sample counts, capitalization templates, finalized negative penalties and the weights of every label are cleared. The `GenerationConfig` and any generator set by `seed` are kept.
+    pub fn reset(&mut self) {
+        self.positive_char_samples.reset();
+        self.negative_char_samples.reset();
+        self.positive_char_type_samples.reset();
+        self.negative_char_type_samples.reset();
+        self.name_sizes.0.clear();
+        self.name_sizes.0.push(0);
+        self.name_sizes.1 = 0;
+        self.labeled_name_sizes.clear();
+        self.labeled_experiments.clear();
+        self.positive_sample_count = 0;
+        self.negative_sample_count = 0;
+        self.capitalization_templates.clear();
+        self.negative_penalties = None;
+        self.lower_order_counts = OnceLock::new();
+    }
+    /// Ages out older observations, for an experiment that keeps reading samples as they arrive. Every character weight, character type weight and length histogram entry
+    /// (including those of every label read through `read_positive_sample_for`) is scaled by `factor` and rounded to the nearest count, and the sums are recomputed from the scaled counts. A count above one is never rounded down to zero, so a rare
+    /// observation fades rather than vanishing in a single call. Repeated decay never increases a count, and a count of one is only forgotten once `factor` is below `0.5`.
+    /// 
+    /// Unlike easing, which smooths probabilities, decay forgets. Sample counts are not affected and finalized negative penalties are discarded (see `finalize_negatives`).
+    /// Produces an Err without changing anything if `factor` is not above `0.0` and at most `1.0`, or if the weight store can not decay its observations. Use `reset` to forget everything.
+    pub fn decay(&mut self, factor: f64) -> Result<(), String> {
+        ngramweights::check_decay_factor(factor)?;
+        self.positive_char_samples.decay(factor)?;
+        self.negative_char_samples.decay(factor)?;
+        self.positive_char_type_samples.decay(factor)?;
+        self.negative_char_type_samples.decay(factor)?;
+        let decay_sizes = |(histogram, total): &mut SizeDistribution| {
+            for count in histogram.iter_mut() {
+                *count = ngramweights::decayed_count(*count, factor);
+            }
+            *total = histogram.iter().sum();
+        };
+        decay_sizes(&mut self.name_sizes);
+        self.labeled_name_sizes.values_mut().for_each(decay_sizes);
+        for labeled in self.labeled_experiments.values_mut() {
+            labeled.decay(factor)?;
         }
+        self.negative_penalties = None;
+        self.lower_order_counts = OnceLock::new();
+        Ok(())
     }
-    fn add_to_sizes_distribution(&mut self, chars: &[ValidChar]) -> () {
-        while chars.len() > self.name_sizes.0.len()-1 {
-            self.name_sizes.0.push(0);
+    /// Records a name of `length` characters. Syllable breaks are annotations rather than letters and should not be counted towards `length`
+    fn add_to_sizes_distribution(&mut self, length: usize, label: Option<&str>) {
+        let add_length = |sizes: &mut SizeDistribution| {
+            while length > sizes.0.len()-1 {
+                sizes.0.push(0);
+            }
+            sizes.0[length] += 1;
+            sizes.1 += 1;
+        };
+        add_length(&mut self.name_sizes);
+        if let Some(label) = label {
+            add_length(self.labeled_name_sizes.entry(label.to_string()).or_insert_with(|| (vec![0], 0)));
         }
-        self.name_sizes.0[chars.len()] += 1;
-        self.name_sizes.1 += 1;
     }
-    fn read_sample(&mut self, text: &[Option<char>], test_type: TestType) -> Result<(),String> {
-        let mut i = 0;
-        let mut valid_chars: Vec<ValidChar> = Vec::with_capacity(text.len());
+    fn read_sample(&mut self, text: &[Option<char>], test_type: TestType, label: Option<&str>) -> Result<(),String> {
         let char_weights = match test_type {
             TestType::Pos => &mut self.positive_char_samples,
             TestType::Neg => &mut self.negative_char_samples,
@@ -141,59 +313,276 @@ impl<const N: usize> NameExperiments<N> {
             TestType::Pos => &mut self.positive_char_type_samples,
             TestType::Neg => &mut self.negative_char_type_samples,
         };
-        // add ngrams of characters from sample to weights
+        // add ngrams of characters and of their character types from sample to weights in a single pass
+        // A right padded name leads with None. Reading starts from the first Some and stops at the next None (or the end of the slice)
+        // A terminator character (see `ValidChar::is_terminator`) is read as a None
+        let text_char = |c: &Option<char>| c.filter(|&c| !ValidChar::is_terminator(c));
+        let start = text.iter().position(|c| text_char(c).is_some()).unwrap_or(text.len());
         let mut n_gram = [ValidChar::null; N];
-        while let Some(p_char) = text[i] {
+        // A rolling classification window, equivalent to `CharType::window` over the characters read so far
+        let mut char_window = [ValidChar::null; CharType::WINDOW];
+        let mut char_type_slice = [CharType::Null; N];
+        let mut last_char: Option<ValidChar> = None;
+        let mut capitals: Vec<u8> = vec![];
+        let mut letter_position = 0usize;
+        for p_char in text[start..].iter().map_while(text_char) {
+            if p_char.is_uppercase() {
+                if let Ok(position) = u8::try_from(letter_position) {capitals.push(position);}
+            }
             let p_char = &ValidChar::try_from(&p_char).unwrap_or(ValidChar::null);
-            let _ = char_weights.add_to_weights(&n_gram,p_char);
+            if *p_char != ValidChar::syllable_break {letter_position += 1;}
+            // A weight that has reached `Counter::MAX` saturates. The Err only reports that the observation was dropped from the weight and its sum, so reading carries on
+            let _ = char_weights.add(&n_gram,p_char);
             n_gram.rotate_left(1);
             n_gram[N-1] = *p_char;
-            valid_chars.push(*p_char);
-            i += 1;
+            char_window.rotate_left(1);
+            char_window[CharType::WINDOW-1] = *p_char;
+            let char_type = CharType::try_from(&char_window)?;
+            let _ = char_type_weights.add_to_weights(&char_type_slice, &char_type);
+            char_type_slice.rotate_left(1);
+            char_type_slice[N-1] = char_type;
+            last_char = Some(*p_char);
         }
-        {
-            // the last ngram should terminate the word. It needs to be added
+        // the last ngram should terminate the word. It needs to be added, unless the last character read (e.g. an invalid character) already terminated it
+        if last_char != Some(ValidChar::null) {
             let p_char = ValidChar::null;
-            let _ = char_weights.add_to_weights(&n_gram,&p_char);
-        }
-        // Make an array of character types using the previously derived valid chars
-        let mut char_types: Vec<CharType> = Vec::with_capacity(text.len());
-        for i in 0..valid_chars.len() {
-            let mut char_slice = [ValidChar::null; 4];
-            for j in 0..char_slice.len() {
-                if (j+1)>i {continue;}
-                char_slice[4-(j+1)] = valid_chars[i-(j+1)];
-            }
-            let char_type = CharType::try_from(&char_slice)?;
-            char_types.push(char_type);
+            let _ = char_weights.add(&n_gram,&p_char);
         }
-        // add ngrams of character types to their weights
-        let mut char_type_slice = [CharType::Null; N];
-        for i in 0..char_types.len() {
-            let p_char= char_types[i];
-            let _ = char_type_weights.add_to_weights(&char_type_slice, &p_char);
-            char_type_slice.rotate_left(1);
-            char_type_slice[N-1] = p_char;
+        // Syllable breaks were not counted as letters
+        self.add_to_sizes_distribution(letter_position, label);
+        match test_type {
+            TestType::Pos => {
+                self.positive_sample_count += 1;
+                self.add_capitalization_template(capitals);
+                self.lower_order_counts = OnceLock::new();
+            },
+            TestType::Neg => {
+                self.negative_sample_count += 1;
+                self.negative_penalties = None;
+            },
         }
-        self.add_to_sizes_distribution(&valid_chars);
         Ok(())
     }
     /// Reads a sample and applies it to the positive test case weights matrix
+    /// 
+    /// Both left padded and right padded text is accepted (see `PaddingBias`). Leading `None` values are skipped and reading stops at the first `None` after the text.
+    /// `ValidChar::TERMINATOR` is treated the same as `None`.
+    /// 
+    /// Reading never fails because a weight is full. A weight that has counted `Counter::MAX` observations (`255` with the default store) saturates: further observations
+    /// are dropped from both the weight and the sum of its row. Within a saturated row the most common continuations are under-represented relative to rarer ones,
+    /// so a corpus large enough to saturate weights is better read into a store with a wider `Counter` (see `NGramWeights`).
     pub fn read_positive_sample(&mut self, text: &[Option<char>]) -> Result<(),String> {
-        self.read_sample(text, TestType::Pos)
+        self.read_sample(text, TestType::Pos, None)
     }
-    /// Reads a sample and applies it to the negative test case weights matrix
+    /// Reads a sample and applies it to the negative test case weights matrix. Weights saturate as described in `read_positive_sample`
     pub fn read_negative_sample(&mut self, text: &[Option<char>]) -> Result<(),String> {
-        self.read_sample(text, TestType::Neg)
+        self.read_sample(text, TestType::Neg, None)
+    }
+    /// Behaves like `read_positive_sample`, but the length of the sample is also recorded against `label`. See `build_random_name_for`.
+    /// 
+    /// Labels are open to a user of the API (e.g. a gender identity or a culture label from `Name`). Character weights are shared between all labels.
+    pub fn read_labeled_positive_sample(&mut self, text: &[Option<char>], label: &str) -> Result<(),String> {
+        self.read_sample(text, TestType::Pos, Some(label))
+    }
+    /// Behaves like `read_negative_sample`, but the length of the sample is also recorded against `label`.
+    pub fn read_labeled_negative_sample(&mut self, text: &[Option<char>], label: &str) -> Result<(),String> {
+        self.read_sample(text, TestType::Neg, Some(label))
+    }
+    /// Behaves like `read_labeled_positive_sample`, but the sample is also read into character weights kept for `label` alone, so that `build_random_name_for` generates
+    /// names from the samples of that label (e.g. a female name from a model of mixed names, keyed by the `gender_identity` of a `Name`). The experiment itself still reads every sample.
+    /// 
+    /// > Note: Every label holds a full set of weights, so each label costs as much memory as another experiment of the same `N` (over 2 MB for `N = 3` with the default store,
+    /// > growing as O(31^N)). Negative samples are not read into the weights of a label.
+    pub fn read_positive_sample_for(&mut self, text: &[Option<char>], label: &str) -> Result<(),String> {
+        self.read_sample(text, TestType::Pos, Some(label))?;
+        if !self.labeled_experiments.contains_key(label) {
+            // The weights of a label hold the same values as the weights of the experiment, e.g. the same alphabet
+            let mut labeled = Self::from_weights(
+                self.positive_char_samples.empty_like()?,
+                self.negative_char_samples.empty_like()?,
+                NGramWeights::try_new()?,
+                NGramWeights::try_new()?,
+            )?;
+            labeled.generation_config = self.generation_config;
+            self.labeled_experiments.insert(label.to_string(), labeled);
+        }
+        self.labeled_experiments.get_mut(label).ok_or(format!("No weights are kept for the label {label}"))?.read_positive_sample(text)
+    }
+    /// `skip_unmappable` decides what happens to a line with a character that does not map to a `ValidChar`: `None` reads the character as a break in the name,
+    /// `Some(true)` skips the line and `Some(false)` produces an Err
+    fn read_stream<R: BufRead>(&mut self, reader: R, test_type: TestType, skip_unmappable: Option<bool>) -> Result<usize, String> {
+        let mut trained = 0usize;
+        let mut text: Vec<Option<char>> = Vec::with_capacity(Self::MAX_NAME_LENGTH + 1);
+        for (line_number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| format!("Failed to read line from stream: {e}"))?;
+            let line = line.trim();
+            if line.is_empty() || line.chars().count() > Self::MAX_NAME_LENGTH {continue;}
+            if let Some(skip) = skip_unmappable {
+                let unmappable = line.chars().find(|&c| !ValidChar::is_terminator(c) && ValidChar::try_from(&c).is_err());
+                match unmappable {
+                    Some(_) if skip => continue,
+                    Some(c) => return Err(format!("Line {} of the stream has the character {c:?}, which can not be mapped to a ValidChar", line_number + 1)),
+                    None => {},
+                }
+            }
+            text.clear();
+            text.extend(line.chars().map(Some));
+            text.push(None);
+            self.read_sample(&text, test_type, None)?;
+            trained += 1;
+        }
+        Ok(trained)
+    }
+    /// Reads names line by line from a `BufRead` source and applies each to the positive test case weights matrix. Returns the number of names that were read.
+    /// 
+    /// Leading and trailing whitespace is trimmed from each line. Blank lines and lines longer than `MAX_NAME_LENGTH` characters are skipped and are not included in the returned count.
+    /// The corpus is never held in memory as a whole. An Err is produced if the underlying reader fails.
+    pub fn train_positive_stream<R: BufRead>(&mut self, reader: R) -> Result<usize, String> {
+        self.read_stream(reader, TestType::Pos, None)
+    }
+    /// Behaves like `train_positive_stream`, but a line with a character that can not be mapped to a `ValidChar` (see `ValidChar::try_from`) is not read.
+    /// If `skip_unmappable` is `true` such lines are skipped and are not included in the returned count. Otherwise an Err naming the line is produced, and the names before it remain read.
+    /// 
+    /// Each line is converted to text the same way `Name::new` converts its text, so terminator characters end the name.
+    pub fn read_positive_from_reader<R: BufRead>(&mut self, reader: R, skip_unmappable: bool) -> Result<usize, String> {
+        self.read_stream(reader, TestType::Pos, Some(skip_unmappable))
+    }
+    /// Reads names line by line from a `BufRead` source and applies each to the negative test case weights matrix. Returns the number of names that were read.
+    /// 
+    /// Follows the same rules as `train_positive_stream`.
+    pub fn train_negative_stream<R: BufRead>(&mut self, reader: R) -> Result<usize, String> {
+        self.read_stream(reader, TestType::Neg, None)
+    }
+    /// Reads `(name, is_valid)` pairs, applying each name to the positive test case weights matrix if `is_valid` is `true` and to the negative test case weights matrix otherwise.
+    /// Returns the number of positive and negative names that were read.
+    ///
+    /// Follows the same rules as `train_positive_stream`.
+    pub fn train_labeled<'s, I>(&mut self, samples: I) -> Result<(usize, usize), String> where I: IntoIterator<Item = (&'s str, bool)> {
+        let mut positives = 0usize;
+        let mut negatives = 0usize;
+        let mut text: Vec<Option<char>> = Vec::with_capacity(Self::MAX_NAME_LENGTH + 1);
+        for (name, is_valid) in samples {
+            let name = name.trim();
+            if name.is_empty() || name.chars().count() > Self::MAX_NAME_LENGTH {continue;}
+            text.clear();
+            text.extend(name.chars().map(Some));
+            text.push(None);
+            if is_valid {
+                self.read_sample(&text, TestType::Pos, None)?;
+                positives += 1;
+            } else {
+                self.read_sample(&text, TestType::Neg, None)?;
+                negatives += 1;
+            }
+        }
+        Ok((positives, negatives))
+    }
+    /// Makes generation reproducible from now on. Every character picked by this experiment (`build_random_name`, `guess_next_char`, `sample_diversity`, etc.) draws from a random
+    /// number generator owned by the experiment and seeded with `seed`, rather than from the global generator. Seeding again with the same value restarts the same sequence of names.
+    /// 
+    /// Generation methods keep taking `&self`. The owned generator sits behind a `Mutex`, so an experiment (or an `InferenceModel` borrowed from it) can still be shared between threads,
+    /// but threads generating from one seeded experiment take turns and the order of their calls decides which names each of them receive.
+    pub fn seed(&mut self, seed: u64) {
+        *self.rng.get_mut().unwrap_or_else(|e| e.into_inner()) = Some(Rng::with_seed(seed));
+    }
+    /// Drops the generator set by `seed`. Generation falls back to the global random number generator.
+    pub fn clear_seed(&mut self) {
+        *self.rng.get_mut().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+    /// Runs `pick` with the generator set by `seed`, or a generator seeded from the global generator if there is none
+    fn with_rng<T>(&self, pick: impl FnOnce(&mut Rng) -> T) -> T {
+        let mut seeded = self.rng.lock().unwrap_or_else(|e| e.into_inner());
+        match seeded.as_mut() {
+            Some(rng) => pick(rng),
+            None => pick(&mut Rng::new()),
+        }
+    }
+    /// Runs `pick` with `rng` if one is given. Otherwise behaves like `with_rng`
+    fn with_rng_or<T>(&self, rng: Option<&mut (dyn RandomSource + '_)>, pick: impl FnOnce(&mut dyn RandomSource) -> T) -> T {
+        match rng {
+            Some(rng) => pick(rng),
+            None => self.with_rng(|rng| pick(rng)),
+        }
+    }
+    /// The number of samples that have been read through `read_positive_sample`
+    pub fn total_positive_samples(&self) -> usize {
+        self.positive_sample_count
+    }
+    /// The number of samples that have been read through `read_negative_sample`
+    pub fn total_negative_samples(&self) -> usize {
+        self.negative_sample_count
+    }
+    /// The number of names that have contributed to the name length distribution. Positive and negative samples are both counted.
+    /// 
+    /// > Note: No de-duplication is applied. A name read twice is counted twice.
+    pub fn distinct_names_seen(&self) -> usize {
+        self.name_sizes.1
+    }
+    /// The share of the names read with each length, indexed by length, e.g. for plotting against the lengths of generated names. Every length from `0` up to the longest name read
+    /// is included and the shares sum to `1.0`. Positive and negative samples are both counted (see `distinct_names_seen`) and syllable breaks do not count towards the length of a name.
+    /// 
+    /// Every share is `0.0` for an experiment that has not read any samples.
+    pub fn length_distribution(&self) -> Vec<f64> {
+        let (histogram, total) = &self.name_sizes;
+        histogram.iter().map(|&count| if *total == 0 {0.0} else {count as f64 / *total as f64}).collect()
+    }
+    /// The mean length of the names read. See `length_distribution`. Produces `0.0` for an experiment that has not read any samples.
+    pub fn mean_length(&self) -> f64 {
+        let (histogram, total) = &self.name_sizes;
+        if *total == 0 {return 0.0;}
+        histogram.iter().enumerate().map(|(length, &count)| (length * count) as f64).sum::<f64>() / *total as f64
+    }
+    /// The most common length of the names read, or the shortest of them if several are equally common. See `length_distribution`.
+    /// Produces `None` for an experiment that has not read any samples.
+    pub fn length_mode(&self) -> Option<usize> {
+        let (histogram, total) = &self.name_sizes;
+        if *total == 0 {return None;}
+        // `max_by_key` keeps the last of equal elements, so the lengths are searched from the longest
+        histogram.iter().enumerate().rev().max_by_key(|&(_, &count)| count).map(|(length, _)| length)
+    }
+    /// The labels that have been read through `read_labeled_positive_sample` or `read_labeled_negative_sample`. In alphabetical order.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.labeled_name_sizes.keys().map(|label| label.as_str())
+    }
+    /// Combines the positive and negative character observations following a character sequence into a (non-normalized) probability for each valid character.
+    /// `pos_easing` holds the positive pseudo-count of each valid character.
+    fn combined_char_probabilities(&self, char_seq: &[ValidChar], pos_easing: &[f64; ValidChar::VARIANTCOUNT as usize], neg_easing_scale: f64, lambdas: Option<&[f64]>) -> Result<[f64; ValidChar::VARIANTCOUNT as usize], String> {
+        let pos_probabilities = self.positive_probabilities(char_seq, pos_easing, lambdas)?;
+        let (neg_chars, neg_char_sum) = (self.negative_char_samples.get_row(char_seq)?, self.negative_char_samples.sum(char_seq)?);
+        let mut combined_char_probabilities: [f64; ValidChar::VARIANTCOUNT as usize] = [0.0; ValidChar::VARIANTCOUNT as usize];
+        if let Some(penalties) = self.finalized_penalties(char_seq) {
+            for i in 0..ValidChar::VARIANTCOUNT as usize {
+                combined_char_probabilities[i] = pos_probabilities[i] * penalties[i].powf(self.generation_config.negative_influence);
+            }
+            return Ok(combined_char_probabilities);
+        }
+        for i in 0..ValidChar::VARIANTCOUNT as usize {
+            let inv_neg_chars_p = neg_char_sum.checked_sub(neg_chars[i].to_usize()).ok_or(format!(
+                "Negative character weights following {char_seq:?} are inconsistent: {:?} was observed {} times, but the row only sums to {neg_char_sum}", ValidChar::ALLCHARS[i], neg_chars[i].to_usize()
+            ))?;
+            // Applying easing to avoid NaNs while combineing negative and positive probabilities.
+            combined_char_probabilities[i] = if neg_char_sum == 0 {
+                pos_probabilities[i]
+            } else {
+                pos_probabilities[i] *
+                    ((inv_neg_chars_p as f64 + pos_easing[i])/ (neg_char_sum as f64 + (neg_easing_scale * ValidChar::VARIANTCOUNT as f64))).powf(self.generation_config.negative_influence)
+            };
+        }
+        Ok(combined_char_probabilities)
     }
     /// Takes a character sequence, a character type sequence, a current count of characters in the word, applies optional positive and easing values and produces a probability distribution over the array of valid characters.
     /// 
     /// ## Parameters
     /// * char_sequence: an array slice of ValidChar to be analysed. Minimum length should be N. Where an experiment of an N character sequence would result in a N+1 character observation.
     /// * char_type_seq: an array slice of CharType to be analysed. Minimum length should be N. Where an experiment of an N character sequence would result in a N+1 character observation.
+    ///   Must be the same length as char_seq. An Err reporting both lengths is produced otherwise.
     /// * character_count: Provide context to the probability distribution of how far along within the name the next guess character would be. Assists with name termination probabilities.
-    /// * pos_easing_scale, neg_easing_scale: Optional parameters to control how much easing is applied to the positive observation cases and how much is applied to the negative observation cases. Defaults to `1.0` if `None` is passed
+    /// * pos_easing_scale, neg_easing_scale: Optional parameters to control how much easing is applied to the positive observation cases and how much is applied to the negative observation cases. Defaults to `1.0` if `None` is passed.
+    ///   Both must be finite and at least `0.0`. An Err naming the offending value is produced otherwise
     /// * square_probabilities: Optional parameter to control if a final square of probabilities is applied to "sharpen" the probability distribution. Can result in a bias to repeat names in the input list, But can assist in reducing the incidence of randomness on the output.
+    ///   A sharpened distribution is scaled so that the most probable character has a value of `1.0`.
+    /// * exclude_null: Optional parameter to answer "assuming the name continues, what's next?". Applied after every other factor. The null (terminating) character is given a probability of `0.0`
+    ///   and the produced sum only covers continuing characters. Defaults to `false` if `None` is passed
     /// 
     /// Use this function if the intent is to combine multiple probability distrubtions and handle letter guessing with other logic.
     /// Defer to using `guess_next_char` if the intent is to resolve to a single character.
@@ -203,41 +592,90 @@ impl<const N: usize> NameExperiments<N> {
     /// 
     /// `(s+1.0)/(n+count_chars)`
     /// 
-    /// where `count_char` is the total number of character choices.
+    /// where `count_char` is the total number of character choices. With `GenerationConfig::backoff_threshold` set, `s` and `n` of a rarely observed context are its backed-off observations.
     /// 
     /// See: [Rule of Succession](https://en.wikipedia.org/wiki/Rule_of_succession)
+    #[allow(clippy::too_many_arguments)]
     pub fn generate_probability_distribution(
         &self,
         char_seq: &[ValidChar],
         char_type_seq: &[CharType], 
-        character_count: u8, 
+        character_count: usize, 
         pos_easing_scale: Option<f64>,
         neg_easing_scale: Option<f64>,
-        square_probabilities: Option<bool>
-    ) -> Result<([f64; ValidChar::VARIANTCOUNT as usize], f64, [ValidChar;4]), String> {
-        let pos_easing_scale = pos_easing_scale.unwrap_or(1.0);
+        square_probabilities: Option<bool>,
+        exclude_null: Option<bool>
+    ) -> Result<([f64; ValidChar::VARIANTCOUNT as usize], f64, [ValidChar; CharType::WINDOW]), String> {
+        self.generate_probability_distribution_with_easing(
+            char_seq, char_type_seq, character_count, &[pos_easing_scale.unwrap_or(1.0); ValidChar::VARIANTCOUNT as usize], neg_easing_scale, square_probabilities, exclude_null
+        )
+    }
+    /// Behaves like `generate_probability_distribution`, but every valid character has its own positive easing (pseudo-count), indexed like `ValidChar::ALLCHARS`.
+    /// This acts as a [Dirichlet prior](https://en.wikipedia.org/wiki/Additive_smoothing) over the characters, e.g. stronger smoothing for rare characters such as q, x and z.
+    /// For a character `i` observed `s` times amongst `n` observations the positive probability will be
+    /// 
+    /// `(s+pos_easing[i])/(n+sum(pos_easing))`
+    /// 
+    /// Character types are eased by the mean of `pos_easing`. Passing the same value for every character is equivalent to passing it as `pos_easing_scale` to `generate_probability_distribution`. Every easing must be finite and at least `0.0`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn generate_probability_distribution_with_easing(
+        &self,
+        char_seq: &[ValidChar],
+        char_type_seq: &[CharType], 
+        character_count: usize, 
+        pos_easing: &[f64; ValidChar::VARIANTCOUNT as usize],
+        neg_easing_scale: Option<f64>,
+        square_probabilities: Option<bool>,
+        exclude_null: Option<bool>
+    ) -> Result<([f64; ValidChar::VARIANTCOUNT as usize], f64, [ValidChar; CharType::WINDOW]), String> {
+        let (mut probabilities, mut sum_of_probabilities, char_window) = self.distribution_with_sizes(
+            char_seq, char_type_seq, character_count, Some(pos_easing), neg_easing_scale, square_probabilities, &self.name_sizes, None
+        )?;
+        if exclude_null.unwrap_or(false) {
+            probabilities[ValidChar::VARIANTCOUNT as usize - 1] = 0.0;
+            sum_of_probabilities = probabilities.iter().sum::<f64>();
+        }
+        Ok((probabilities, sum_of_probabilities, char_window))
+    }
+    /// Body of `generate_probability_distribution`. Name termination probabilities are taken from `name_sizes` so that a labeled length distribution can be used instead of the global one.
+    /// Every positive easing defaults to `1.0` if `pos_easing` is `None`.
+    /// The positive probabilities are interpolated over every context length if `lambdas` are given (see `generate_probability_distribution_interpolated`).
+    #[allow(clippy::too_many_arguments)]
+    fn distribution_with_sizes(
+        &self,
+        char_seq: &[ValidChar],
+        char_type_seq: &[CharType], 
+        character_count: usize, 
+        pos_easing: Option<&[f64; ValidChar::VARIANTCOUNT as usize]>,
+        neg_easing_scale: Option<f64>,
+        square_probabilities: Option<bool>,
+        name_sizes: &SizeDistribution,
+        lambdas: Option<&[f64]>
+    ) -> Result<([f64; ValidChar::VARIANTCOUNT as usize], f64, [ValidChar; CharType::WINDOW]), String> {
+        if char_seq.len() < N || char_type_seq.len() < N || char_seq.len() != char_type_seq.len() {
+            return Err(format!(
+                "Context length mismatch: char_seq and char_type_seq must be the same length and at least N={N}. char_seq has length {}, char_type_seq has length {}",
+                char_seq.len(), char_type_seq.len()
+            ));
+        }
+        let pos_easing = pos_easing.unwrap_or(&[1.0; ValidChar::VARIANTCOUNT as usize]);
+        let pos_easing_scale = pos_easing.iter().sum::<f64>() / ValidChar::VARIANTCOUNT as f64;
         let neg_easing_scale = neg_easing_scale.unwrap_or(1.0);
-        let mut char_4_sequence: [ValidChar; 4] = [ValidChar::null, ValidChar::null, ValidChar::null, ValidChar::null];
-        for i in 0..3 {
-            char_4_sequence[4-2-i] = *char_seq.get(char_seq.len()-1-i).unwrap_or(&ValidChar::null);
+        // A negative easing can produce negative or NaN probabilities. Checked before anything is computed from it
+        if let Some((&easing, ch)) = pos_easing.iter().zip(ValidChar::ALLCHARS.iter()).find(|(&easing, _)| !easing.is_finite() || easing < 0.0) {
+            return Err(format!("Positive easing must be finite and at least 0.0. Found {easing} for {ch:?}"));
         }
+        if !neg_easing_scale.is_finite() || neg_easing_scale < 0.0 {
+            return Err(format!("neg_easing_scale must be finite and at least 0.0. Found {neg_easing_scale}"));
+        }
+        let mut char_window = CharType::window(char_seq, ValidChar::null);
         // Use existing details about the ngrams to produce a probability distribution of the chars without their types factored in.
         // Build a mapping to which predicted characters map to which character types
-        let (pos_chars, pos_char_sum) = self.positive_char_samples.get_row_and_sum(char_seq)?;
-        let (neg_chars, neg_char_sum) = self.negative_char_samples.get_row_and_sum(char_seq)?;
-        let mut combined_char_probabilities: [f64; ValidChar::VARIANTCOUNT as usize] = [0.0; ValidChar::VARIANTCOUNT as usize];
+        let mut combined_char_probabilities = self.combined_char_probabilities(char_seq, pos_easing, neg_easing_scale, lambdas)?;
         let mut char_type_mapping: [Vec<usize>; CharType::VARIANTCOUNT] = [const {vec![]}; CharType::VARIANTCOUNT];
         for i in 0..ValidChar::VARIANTCOUNT as usize {
-            let inv_neg_chars_p = neg_char_sum - (neg_chars[i] as usize);
-            // Applying easing to avoid NaNs while combineing negative and positive probabilities.
-            combined_char_probabilities[i] = if neg_char_sum == 0 {
-                (pos_chars[i] as f64 + pos_easing_scale) / (pos_char_sum as f64 + (pos_easing_scale * ValidChar::VARIANTCOUNT as f64))
-            } else {
-                ((pos_chars[i] as f64 + pos_easing_scale) / (pos_char_sum as f64 + (pos_easing_scale * ValidChar::VARIANTCOUNT as f64))) *
-                    ((inv_neg_chars_p as f64 + pos_easing_scale)/ (neg_char_sum as f64 + (neg_easing_scale * ValidChar::VARIANTCOUNT as f64)))
-            };
-            char_4_sequence[3] = ValidChar::ALLCHARS[i];
-            let mapped_char_type = CharType::try_from(&char_4_sequence)?;
+            char_window[CharType::WINDOW-1] = ValidChar::ALLCHARS[i];
+            let mapped_char_type = CharType::try_from(&char_window)?;
             char_type_mapping[mapped_char_type as usize].push(i);
         }
         // Use existing details about ngrams of character types to build distribution of character types.
@@ -245,86 +683,515 @@ impl<const N: usize> NameExperiments<N> {
         let (pos_char_types, pos_char_type_sum) = self.positive_char_type_samples.get_row_and_sum(char_type_seq)?;
         let (neg_char_types, neg_char_type_sum) = self.negative_char_type_samples.get_row_and_sum(char_type_seq)?;
         for i in 0..CharType::VARIANTCOUNT {
-            let inv_neg_char_type_p = neg_char_type_sum - (neg_char_types[i] as usize);
+            let inv_neg_char_type_p = neg_char_type_sum.checked_sub(neg_char_types[i].to_usize()).ok_or(format!(
+                "Negative character type weights following {char_type_seq:?} are inconsistent: a type was observed {} times, but the row only sums to {neg_char_type_sum}", neg_char_types[i].to_usize()
+            ))?;
             // Applying easing to avoid NaNs while combineing negative and positive probabilities.
-            let combined_type_p  = ((pos_char_types[i] as f64 + pos_easing_scale)/(pos_char_type_sum as f64 + (pos_easing_scale * CharType::VARIANTCOUNT as f64))) *
-                ((inv_neg_char_type_p as f64 + neg_easing_scale)/(neg_char_type_sum as f64 + (neg_easing_scale * CharType::VARIANTCOUNT as f64)));
+            let combined_type_p  = ((pos_char_types[i].to_usize() as f64 + pos_easing_scale)/(pos_char_type_sum as f64 + (pos_easing_scale * CharType::VARIANTCOUNT as f64))) *
+                ((inv_neg_char_type_p as f64 + neg_easing_scale)/(neg_char_type_sum as f64 + (neg_easing_scale * CharType::VARIANTCOUNT as f64))).powf(self.generation_config.negative_influence);
             for &j in char_type_mapping.get(i).unwrap() {
                 combined_char_probabilities[j] *= combined_type_p;
             }
         }
-        // Apply statistics about name endings to the probabilities
+        // Apply statistics about name endings to the probabilities. A name of `character_count` characters may end here, so its own length is included
         {
-            let probability_end_here: f64 = self.name_sizes.0[0..(character_count as usize)].iter().map(|&x| (x as f64)/self.name_sizes.1 as f64).sum();
+            let probability_end_here = termination_probability(name_sizes, character_count);
             let probability_ends_in_future = 1.0 - probability_end_here;
-            // println!("prob ends here: {probability_end_here}, prob ends in future: {probability_ends_in_future}");
             for i in 0..combined_char_probabilities.len()-1 {
                 combined_char_probabilities[i] *= probability_ends_in_future / ValidChar::VARIANTCOUNT as f64;
             }
             combined_char_probabilities[combined_char_probabilities.len()-1] *= probability_end_here;
-            // combined_char_probabilities[combined_char_probabilities.len()-1] = probability_end_here;
+        }
+        // A name never starts with a separator and a space is never followed by another space
+        if character_count == 0 {
+            for separator in [ValidChar::space, ValidChar::dash, ValidChar::apostrophe] {
+                combined_char_probabilities[separator as usize] = 0.0;
+            }
+        } else if char_seq.last() == Some(&ValidChar::space) {
+            combined_char_probabilities[ValidChar::space as usize] = 0.0;
+        }
+        // A syllable break does not lengthen a name, so one is never followed by another. Otherwise a name could repeat them without ever reaching its hard stop
+        if char_seq.last() == Some(&ValidChar::syllable_break) {
+            combined_char_probabilities[ValidChar::syllable_break as usize] = 0.0;
+        }
+        // Characters the weight store can not hold (e.g. outside of an alphabet) are never generated
+        for (i, p) in combined_char_probabilities.iter_mut().enumerate() {
+            if !self.positive_char_samples.contains(i) {
+                *p = 0.0;
+            }
         }
         if square_probabilities.unwrap_or(true) {
+            // The probabilities are products of many small factors. Rescale so the largest is 1.0 before squaring, otherwise every entry can underflow to 0.0
+            let max_probability = combined_char_probabilities.iter().copied().fold(0.0, f64::max);
+            if max_probability > 0.0 && max_probability.is_finite() {
+                for p in combined_char_probabilities.iter_mut() {
+                    *p /= max_probability;
+                }
+            }
             // Square the probabilities
-            for i in 0..combined_char_probabilities.len() {
-                combined_char_probabilities[i] *= combined_char_probabilities[i];
+            for p in combined_char_probabilities.iter_mut() {
+                *p *= *p;
             }
         }
 
-        let sum_of_probabilities = combined_char_probabilities.iter().sum::<f64>();
+        let mut sum_of_probabilities = combined_char_probabilities.iter().sum::<f64>();
         if sum_of_probabilities.is_nan() {
             return Err(format!("Sum of probabilities produced a nan: {combined_char_probabilities:?}"));
         }
-        Ok((combined_char_probabilities, sum_of_probabilities, char_4_sequence))
+        // Characters too unlikely to be worth picking are made impossible. The most probable character always remains so that a pick can be made
+        let epsilon = self.generation_config.epsilon;
+        if epsilon > 0.0 && sum_of_probabilities > 0.0 {
+            let threshold = epsilon * sum_of_probabilities;
+            let max_probability = combined_char_probabilities.iter().copied().fold(0.0, f64::max);
+            for p in combined_char_probabilities.iter_mut() {
+                if *p < threshold && *p < max_probability {
+                    *p = 0.0;
+                }
+            }
+            sum_of_probabilities = combined_char_probabilities.iter().sum::<f64>();
+        }
+        Ok((combined_char_probabilities, sum_of_probabilities, char_window))
 
     }
     /// Takes a character sequence, a character type sequence, the current count of characters in a word, and guesses next character, its corresponding character type. If an error is encountered it produces a String based Err.
     /// 
+    /// Picks with `SamplingStrategy::Proportional`. See `guess_next_char_with_strategy` to pick another way.
+    /// Produces an Err if every character has a probability of `0.0` following `char_seq`, e.g. when a strong `GenerationConfig::negative_influence` rules out every character.
+    /// 
     /// ## Parameters
     /// * char_seq: an array slice of ValidChar to be analysed. Minimum length should be N. Where an experiment of an N character sequence would result in a N+1 character observation.
     /// * char_type_seq: an array slice of CharType to be analysed. Minimum length should be N. Where an experiment of an N character sequence would result in a N+1 character observation.
     /// * current_character_count: Provide context to the probability distribution of how far along within the name the next guess character would be. Assists with name termination probabilities.
     /// 
     ///  
-    pub fn guess_next_char(&self, char_seq: &[ValidChar], char_type_seq: &[CharType], current_char_count: u8) -> Result<(ValidChar, CharType), String> {
-        let (char_probabilities, sum_of_probabilities, mut char_4_sequence) = self.generate_probability_distribution(
+    pub fn guess_next_char(&self, char_seq: &[ValidChar], char_type_seq: &[CharType], current_char_count: usize) -> Result<(ValidChar, CharType), String> {
+        self.guess_next_char_with_strategy(char_seq, char_type_seq, current_char_count, SamplingStrategy::Proportional)
+    }
+    /// Behaves like `guess_next_char`, but the next character is picked from the probability distribution according to `strategy`.
+    pub fn guess_next_char_with_strategy(&self, char_seq: &[ValidChar], char_type_seq: &[CharType], current_char_count: usize, strategy: SamplingStrategy) -> Result<(ValidChar, CharType), String> {
+        let (next_char, next_char_type, _) = self.guess_with_sizes(char_seq, char_type_seq, current_char_count, strategy, &self.name_sizes, 0, None)?;
+        Ok((next_char, next_char_type))
+    }
+    /// Behaves like `guess_next_char`, but the next character is picked from `rng` rather than from the generator set by `seed` or the global generator.
+    /// Identically seeded generators produce identical guesses.
+    pub fn guess_next_char_seeded(&self, char_seq: &[ValidChar], char_type_seq: &[CharType], current_char_count: usize, rng: &mut impl RandomSource) -> Result<(ValidChar, CharType), String> {
+        let (next_char, next_char_type, _) = self.guess_with_sizes(char_seq, char_type_seq, current_char_count, SamplingStrategy::Proportional, &self.name_sizes, 0, Some(rng))?;
+        Ok((next_char, next_char_type))
+    }
+    /// The `k` most probable characters to follow `char_seq`, paired with their share of the distribution from `generate_probability_distribution` (with default settings), most probable first.
+    /// Intended for suggesting the next letter while a name is typed. Ties are ordered by `ValidChar` index so that suggestions are stable, and characters with a probability of `0.0` are left out.
+    /// Produces an Err if no character can follow `char_seq`.
+    /// 
+    /// ## Parameters
+    /// * char_seq, char_type_seq, current_char_count: See `guess_next_char`
+    /// * k: The most characters produced
+    pub fn top_k_next(&self, char_seq: &[ValidChar], char_type_seq: &[CharType], current_char_count: usize, k: usize) -> Result<Vec<(ValidChar, f64)>, String> {
+        let (probabilities, sum_of_probabilities, _) = self.generate_probability_distribution(char_seq, char_type_seq, current_char_count, None, None, None, None)?;
+        if sum_of_probabilities <= 0.0 || !sum_of_probabilities.is_finite() {
+            return Err(format!("No character can follow {char_seq:?}"));
+        }
+        let mut ranked: Vec<(ValidChar, f64)> = ValidChar::ALLCHARS.iter().zip(probabilities.iter())
+            .filter(|(_, &p)| p > 0.0)
+            .map(|(&ch, &p)| (ch, p / sum_of_probabilities))
+            .collect();
+        // A stable sort, so tied characters keep their `ValidChar` order
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        ranked.truncate(k);
+        Ok(ranked)
+    }
+    /// Behaves like `generate_probability_distribution`, but the distribution is normalized and keyed by the `char` of each valid character, for logging or visualizing
+    /// without knowing the order of `ValidChar`. Every valid character is included, and the null (terminating) character is keyed by `'\0'`. Produces an Err if the distribution sums to `0.0`.
+    /// 
+    /// Intended for debugging and tooling. Prefer `generate_probability_distribution` while generating.
+    #[allow(clippy::too_many_arguments)]
+    pub fn distribution_as_map(
+        &self,
+        char_seq: &[ValidChar],
+        char_type_seq: &[CharType],
+        character_count: usize,
+        pos_easing_scale: Option<f64>,
+        neg_easing_scale: Option<f64>,
+        square_probabilities: Option<bool>,
+        exclude_null: Option<bool>
+    ) -> Result<BTreeMap<char, f64>, String> {
+        let (probabilities, sum_of_probabilities, _) = self.generate_probability_distribution(
+            char_seq, char_type_seq, character_count, pos_easing_scale, neg_easing_scale, square_probabilities, exclude_null
+        )?;
+        if sum_of_probabilities <= 0.0 || !sum_of_probabilities.is_finite() {
+            return Err(format!("The distribution following {char_seq:?} can not be normalized. Its probabilities sum to {sum_of_probabilities}"));
+        }
+        Ok(ValidChar::ALLCHARS.iter().zip(probabilities.iter()).map(|(&ch, &p)| (char::from(ch), p / sum_of_probabilities)).collect())
+    }
+    /// Picks from `rng` if one is given. Otherwise see `with_rng`. The share of the distribution held by the most probable continuing (non-null) character is produced with the pick.
+    /// The null character is not picked while `current_char_count` is below `min_length`, unless nothing else can be picked.
+    /// Produces an Err if the distribution sums to (nearly) `0.0` rather than picking a character that is not possible.
+    #[allow(clippy::too_many_arguments)]
+    fn guess_with_sizes(&self, char_seq: &[ValidChar], char_type_seq: &[CharType], current_char_count: usize, strategy: SamplingStrategy, name_sizes: &SizeDistribution, min_length: usize, rng: Option<&mut (dyn RandomSource + '_)>) -> Result<(ValidChar, CharType, f64), String> {
+        let (mut char_probabilities, mut sum_of_probabilities, mut char_window) = self.distribution_with_sizes(
             char_seq, char_type_seq, 
             current_char_count, 
             None, 
             None,
+            None,
+            name_sizes,
             None
         )?;
-        // println!("p: {char_probabilities:?}, p_sum: {sum_of_probabilities}, 4char_sequence: {char_4_sequence:?}");
+        if current_char_count < min_length && sum_of_probabilities > char_probabilities[ValidChar::null as usize] {
+            char_probabilities[ValidChar::null as usize] = 0.0;
+            sum_of_probabilities = char_probabilities.iter().sum::<f64>();
+        }
+        // println!("p: {char_probabilities:?}, p_sum: {sum_of_probabilities}, char_window: {char_window:?}");
         // println!("");
-        let mut random_pick = rand_float() * sum_of_probabilities;
-        let pick_start = random_pick;
-        let index_pick  = char_probabilities.into_iter().enumerate().find_map(|(i, p)| {
-            if p >= random_pick {return Some(i)} else {
-                random_pick -= p;
-                None
-            }
-        }).ok_or(format!("Random pick failed to pick a value. pick:{pick_start}, sum_of_probabilities: {sum_of_probabilities}"))?;
-        char_4_sequence[3] = ValidChar::ALLCHARS[index_pick];
-        let picked_char_type = CharType::try_from(&char_4_sequence)?;
-        Ok((ValidChar::ALLCHARS[index_pick], picked_char_type))
+        if sum_of_probabilities <= 0.0 || !sum_of_probabilities.is_normal() {
+            return Err(format!("No character can follow {char_seq:?}. Every character has a probability of 0.0 (the probabilities sum to {sum_of_probabilities})"));
+        }
+        let mut picked_from = char_probabilities;
+        let picked_from_sum = self.generation_config.apply_temperature(&mut picked_from, sum_of_probabilities)?;
+        let index_pick = self.with_rng_or(rng, |rng| strategy.pick(&picked_from, picked_from_sum, rng))?;
+        char_window[CharType::WINDOW-1] = ValidChar::ALLCHARS[index_pick];
+        let picked_char_type = CharType::try_from(&char_window)?;
+        let max_continuing = char_probabilities[..ValidChar::null as usize].iter().copied().fold(0.0, f64::max);
+        let continuing_share = if sum_of_probabilities > 0.0 {max_continuing / sum_of_probabilities} else {0.0};
+        Ok((ValidChar::ALLCHARS[index_pick], picked_char_type, continuing_share))
     }
     /// Using the existing positive and negative weights the system will repetitively guess names until it encounteres a null character. Once the loop guesses a null character the function returns a resulting name in all lowercase letters as a String. If the function encounters an error it will produce a string based Err.
     /// 
+    /// Picks every character with `SamplingStrategy::Proportional`. See `build_random_name_with_strategy` to pick another way.
+    /// 
     /// ## Parameters
     /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
     pub fn build_random_name(&self, hard_stop: Option<u8>) -> Result<String,String> {
+        self.build_random_name_with_strategy(hard_stop, SamplingStrategy::Proportional)
+    }
+    /// Generates `count` names with `build_random_name`, in the order they were generated. Names may repeat, see `build_distinct_names` for a batch without duplicates.
+    /// Produces an Err as soon as generating any name produces an Err.
+    /// 
+    /// ## Parameters
+    /// * count: The number of names to generate
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    pub fn build_random_names(&self, count: usize, hard_stop: Option<u8>) -> Result<Vec<String>,String> {
+        (0..count).map(|_| self.build_random_name(hard_stop)).collect()
+    }
+    /// An endless iterator of names generated lazily with `build_random_name`, one for every call to `next`. Combine it with `take`, `filter` or `filter_map` in place of a manual loop,
+    /// e.g. `experiments.names(Some(12)).take(10).collect::<Result<Vec<String>, String>>()`. Generation failures are yielded as `Err` and do not end the iterator.
+    /// 
+    /// ## Parameters
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    pub fn names(&self, hard_stop: Option<u8>) -> impl Iterator<Item = Result<String,String>> + '_ {
+        std::iter::repeat_with(move || self.build_random_name(hard_stop))
+    }
+    /// Behaves like `build_random_name`, but every character is picked according to `strategy`.
+    /// 
+    /// > Note: `SamplingStrategy::Greedy` always produces the same name for a given set of weights.
+    pub fn build_random_name_with_strategy(&self, hard_stop: Option<u8>, strategy: SamplingStrategy) -> Result<String,String> {
+        let mut name_string = String::new();
+        self.build_name_into(&mut name_string, hard_stop, strategy, &self.name_sizes, &[], None)?;
+        Ok(name_string)
+    }
+    /// Behaves like `build_random_name`, but every character is picked from `rng` rather than from the generator set by `seed` or the global generator.
+    /// Intended for deterministic generation from a seed the caller owns, e.g. one seed per generated world. Identically seeded generators produce identical names.
+    /// 
+    /// ## Parameters
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    /// * rng: The generator to pick characters from, e.g. a `fastrand::Rng` or any other `RandomSource`
+    pub fn build_random_name_seeded(&self, hard_stop: Option<u8>, rng: &mut impl RandomSource) -> Result<String,String> {
+        let mut name_string = String::new();
+        self.build_name_into(&mut name_string, hard_stop, SamplingStrategy::Proportional, &self.name_sizes, &[], Some(rng))?;
+        Ok(name_string)
+    }
+    /// Behaves like `build_random_name`, but the name is generated from the weights kept for `label` if samples were read with `read_positive_sample_for`.
+    /// Otherwise the character weights of the whole experiment are used, and only the name terminates according to the lengths of names read with `label`.
+    /// Produces an Err if no sample has been read with `label`.
+    /// 
+    /// ## Parameters
+    /// * label: A label previously passed to `read_positive_sample_for`, `read_labeled_positive_sample` or `read_labeled_negative_sample`
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    pub fn build_random_name_for(&self, label: &str, hard_stop: Option<u8>) -> Result<String,String> {
+        let mut name_string = String::new();
+        if let Some(labeled) = self.labeled_experiments.get(label) {
+            // Picks draw from this experiment, so that a generator set by `seed` applies to every label
+            self.with_rng(|rng| labeled.build_name_into(&mut name_string, hard_stop, SamplingStrategy::Proportional, &labeled.name_sizes, &[], Some(rng)))?;
+            return Ok(name_string);
+        }
+        let name_sizes = self.labeled_name_sizes.get(label).ok_or(format!("No samples have been read with the label {label}"))?;
+        self.build_name_into(&mut name_string, hard_stop, SamplingStrategy::Proportional, name_sizes, &[], None)?;
+        Ok(name_string)
+    }
+    /// Behaves like `build_random_name`, but the name is produced as a left padded `Name` with the labels (gender identity, culture, sentiment and family) of `labels` copied in,
+    /// so that generated names keep the same provenance as the names read. The text of `labels` is ignored.
+    /// 
+    /// The name never exceeds `M-1` characters, which applies a lower `hard_stop` where necessary. Produces an Err if `M` is less than `2`.
+    /// 
+    /// ## Parameters
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    /// * labels: The name to copy labels from
+    pub fn build_random_name_labeled<const L: usize, const M: usize>(&self, hard_stop: Option<u8>, labels: &Name<L>) -> Result<Name<M>,String> {
+        let capacity = u8::try_from(M.saturating_sub(1)).unwrap_or(u8::MAX);
+        if capacity == 0 {
+            return Err(format!("A Name<{M}> can not hold a generated name"));
+        }
+        let hard_stop = hard_stop.unwrap_or(16).min(capacity);
+        let name_string = self.build_random_name(Some(hard_stop))?;
+        let mut text = [None; M];
+        for (slot, c) in text.iter_mut().zip(name_string.chars()) {
+            *slot = Some(c);
+        }
+        Ok(Name {
+            text,
+            gender_identity: labels.gender_identity,
+            major_culture_label: labels.major_culture_label,
+            minor_culture_label: labels.minor_culture_label,
+            sentiment_label: labels.sentiment_label,
+            family_label: labels.family_label,
+        })
+    }
+    /// Generates a name in the style of an exemplar, e.g. "give me a name like Grukthar". The name starts with the first `N-1` characters of `like` and the rest is guessed as in
+    /// `build_random_name`. Name termination is biased so that names of the same length as `like` are as likely as every other observed length combined.
+    /// 
+    /// Produces an Err if `like` is empty or contains a character that is not a `ValidChar`. Characters are not coerced to null.
+    /// > Note: Nothing prevents the exemplar itself from being produced, especially from a small body of names.
+    /// 
+    /// ## Parameters
+    /// * like: The exemplar name
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    pub fn build_similar_name(&self, like: &str, hard_stop: Option<u8>) -> Result<String,String> {
+        let exemplar: Vec<ValidChar> = like.chars().map(|c| ValidChar::try_from(&c).and_then(|ch| {
+            if ch == ValidChar::null {Err(format!("{c} is not a valid character"))} else {Ok(ch)}
+        })).collect::<Result<_,_>>()?;
+        let length = exemplar.iter().filter(|&&ch| ch != ValidChar::syllable_break).count();
+        if length == 0 {
+            return Err("An exemplar name must have at least one character".to_string());
+        }
+        let mut name_sizes = self.name_sizes.clone();
+        while length > name_sizes.0.len()-1 {
+            name_sizes.0.push(0);
+        }
+        let bias = name_sizes.1.max(1);
+        name_sizes.0[length] += bias;
+        name_sizes.1 += bias;
+        // The seed never reaches the hard stop so that generation can still terminate there
+        let seed_length = (N-1).min(hard_stop.unwrap_or(16).saturating_sub(1) as usize);
+        let seed_end = exemplar.iter().enumerate()
+            .filter(|&(_, &ch)| ch != ValidChar::syllable_break)
+            .nth(seed_length)
+            .map_or(exemplar.len(), |(i, _)| i);
+        let mut name_string = String::new();
+        self.build_name_into(&mut name_string, hard_stop, SamplingStrategy::Proportional, &name_sizes, &exemplar[..seed_end], None)?;
+        Ok(name_string)
+    }
+    /// Behaves like `build_random_name`, but the first character is picked only from `start_filter` (e.g. only vowels), in proportion to how often each starts a name.
+    /// Every character after the first is picked as usual. `None` allows every character, as in `build_random_name`.
+    ///
+    /// The null character and `ValidChar::syllable_break` never start a name, even if they are in `start_filter`.
+    /// Produces an Err if every character of `start_filter` has a probability of `0.0` of starting a name (e.g. if `start_filter` is empty).
+    ///
+    /// ## Parameters
+    /// * start_filter: The characters a name may start with
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    pub fn build_random_name_starting_with(&self, start_filter: Option<&[ValidChar]>, hard_stop: Option<u8>) -> Result<String,String> {
+        let mut name_string = String::new();
+        if hard_stop == Some(0) {return Ok(name_string);}
+        let (mut char_probabilities, _, _) = self.distribution_with_sizes(
+            &[ValidChar::null; N], &[CharType::Null; N], 0, None, None, None, &self.name_sizes, None
+        )?;
+        for (i, p) in char_probabilities.iter_mut().enumerate() {
+            let ch = ValidChar::ALLCHARS[i];
+            if ch == ValidChar::null || ch == ValidChar::syllable_break || start_filter.is_some_and(|allowed| !allowed.contains(&ch)) {
+                *p = 0.0;
+            }
+        }
+        let sum_of_probabilities: f64 = char_probabilities.iter().sum();
+        if sum_of_probabilities <= 0.0 || !sum_of_probabilities.is_normal() {
+            return Err(format!("No name can start with any of {start_filter:?}. Every allowed character has a probability of 0.0"));
+        }
+        let picked_from_sum = self.generation_config.apply_temperature(&mut char_probabilities, sum_of_probabilities)?;
+        let index_pick = self.with_rng(|rng| SamplingStrategy::Proportional.pick(&char_probabilities, picked_from_sum, rng))?;
+        self.build_name_into(&mut name_string, hard_stop, SamplingStrategy::Proportional, &self.name_sizes, &[ValidChar::ALLCHARS[index_pick]], None)?;
+        Ok(name_string)
+    }
+    /// The length of `name_string` once trailing spaces (and other separators with `GenerationConfig::trim_trailing_separators`) are trimmed, as they are from a generated name
+    fn trimmed_length(&self, name_string: &str) -> usize {
+        // A space only separates the parts of a compound name
+        if self.generation_config.trim_trailing_separators {
+            name_string.trim_end_matches(|c: char| c.is_whitespace() || c == '-' || c == '\'' || c == ValidChar::TERMINATOR).len()
+        } else {
+            name_string.trim_end().len()
+        }
+    }
+    /// Generation loop behind `build_random_name`. Characters are pushed onto `name_string` as they are guessed so that a partial name survives an Err.
+    /// The name starts with the characters of `seed`, which also form the initial context for guessing. Characters are picked from `rng` if one is given.
+    #[allow(clippy::too_many_arguments)]
+    fn build_name_into(&self, name_string: &mut String, hard_stop: Option<u8>, strategy: SamplingStrategy, name_sizes: &SizeDistribution, seed: &[ValidChar], mut rng: Option<&mut (dyn RandomSource + '_)>) -> Result<(),String> {
         let mut char_type_array: [CharType; N] = [CharType::Null;N];
         let mut char_array: [ValidChar; N] = [ValidChar::null;N];
-        let mut name_string = String::new();
-        let (mut next_char, mut next_char_type) = self.guess_next_char(&char_array, &char_type_array, name_string.len() as u8)?;
-        while next_char != ValidChar::null && name_string.len() != hard_stop.unwrap_or(16) as usize {
-            name_string.push(char::from(next_char));
+        // The number of characters in `name_string`, which is not its length in bytes once it holds multibyte characters
+        let mut char_count = name_string.chars().count();
+        for (i, &seed_char) in seed.iter().enumerate() {
+            let seed_char_type = CharType::try_from(&CharType::window(&seed[..i], seed_char))?;
+            if seed_char != ValidChar::syllable_break {
+                name_string.push(char::from(seed_char));
+                char_count += 1;
+            }
+            char_array.rotate_left(1);
+            char_array[N-1] = seed_char;
+            char_type_array.rotate_left(1);
+            char_type_array[N-1] = seed_char_type;
+        }
+        let (min_length, max_length) = self.generation_config.length_bounds(name_sizes)?.unwrap_or((0, usize::MAX));
+        let min_length = min_length.max(self.generation_config.min_length.map_or(0, usize::from));
+        let min_length_sizes = self.generation_config.sizes_with_min_length(name_sizes);
+        let name_sizes = min_length_sizes.as_ref().unwrap_or(name_sizes);
+        // Trailing separators are trimmed once the name ends, so they do not count towards a minimum length. A name may end at exactly the minimum length
+        // (see `termination_probability`), so a counted separator would leave it a character short once trimmed
+        let counted_length = |name_string: &str, char_count: usize| if min_length == 0 {char_count} else {
+            char_count - name_string[self.trimmed_length(name_string)..].chars().count()
+        };
+        // The contexts before each guessed character, so that generation can backtrack out of a dead end. Seed characters are never removed
+        let mut history: Vec<([ValidChar; N], [CharType; N], usize, usize)> = vec![];
+        let mut backtracks = 0usize;
+        let (mut next_char, mut next_char_type, mut continuing_share) = self.guess_with_sizes(&char_array, &char_type_array, counted_length(name_string, char_count), strategy, name_sizes, min_length, rng.as_deref_mut())?;
+        loop {
+            if continuing_share < self.generation_config.backtrack_threshold && backtracks < self.generation_config.max_backtracks {
+                if let Some((previous_chars, previous_char_types, previous_length, previous_char_count)) = history.pop() {
+                    (char_array, char_type_array) = (previous_chars, previous_char_types);
+                    name_string.truncate(previous_length);
+                    char_count = previous_char_count;
+                    backtracks += 1;
+                    (next_char, next_char_type, continuing_share) = self.guess_with_sizes(&char_array, &char_type_array, counted_length(name_string, char_count), strategy, name_sizes, min_length, rng.as_deref_mut())?;
+                    continue;
+                }
+            }
+            if next_char == ValidChar::null || char_count == hard_stop.unwrap_or(16) as usize || char_count >= max_length {break;}
+            history.push((char_array, char_type_array, name_string.len(), char_count));
+            // Syllable breaks shape the rest of the name but are not part of the output
+            if next_char != ValidChar::syllable_break {
+                name_string.push(char::from(next_char));
+                char_count += 1;
+            }
             char_array.rotate_left(1);
             char_array[N-1] = next_char;
             char_type_array.rotate_left(1);
             char_type_array[N-1] = next_char_type;
-            (next_char, next_char_type) = self.guess_next_char(&char_array, &char_type_array, name_string.len() as u8)?;
+            (next_char, next_char_type, continuing_share) = self.guess_with_sizes(&char_array, &char_type_array, counted_length(name_string, char_count), strategy, name_sizes, min_length, rng.as_deref_mut())?;
         }
-        Ok(name_string)
+        let trimmed_length = self.trimmed_length(name_string);
+        name_string.truncate(trimmed_length);
+        Ok(())
+    }
+    /// Behaves like `build_random_name` (`SamplingStrategy::Proportional`), but an Err part way through a name causes generation to start over, up to `max_attempts` times in total.
+    /// 
+    /// Produces the name paired with `None` as soon as an attempt succeeds. If every attempt fails the longest partial name is produced instead, paired with the last error as a warning.
+    /// An Err is only produced if `max_attempts` is `0`.
+    /// 
+    /// ## Parameters
+    /// * max_attempts: The number of times a name will be attempted before giving up
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    pub fn build_random_name_resilient(&self, max_attempts: usize, hard_stop: Option<u8>) -> Result<(String, Option<String>),String> {
+        let mut best_partial: Option<(String, String)> = None;
+        for _ in 0..max_attempts {
+            let mut name_string = String::new();
+            match self.build_name_into(&mut name_string, hard_stop, SamplingStrategy::Proportional, &self.name_sizes, &[], None) {
+                Ok(()) => return Ok((name_string, None)),
+                Err(e) => {
+                    if best_partial.as_ref().is_none_or(|(partial, _)| name_string.len() >= partial.len()) {
+                        best_partial = Some((name_string, e));
+                    }
+                }
+            }
+        }
+        let (partial, warning) = best_partial.ok_or("max_attempts must be at least 1".to_string())?;
+        Ok((partial, Some(warning)))
     }
-}
\ No newline at end of file
+    /// Behaves like `build_random_name`, but whole names are generated again until a name is produced that is not a member of `blocklist` and contains none of `blocked_substrings`.
+    /// Comparisons ignore case. Produces an Err if no acceptable name is produced within `max_attempts` names, or if generation itself produces an Err.
+    /// 
+    /// ## Parameters
+    /// * blocklist: Names that must never be produced
+    /// * blocked_substrings: Strings that must never appear anywhere within a produced name
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    /// * max_attempts: The number of names generated before giving up
+    pub fn build_random_name_filtered(&self, blocklist: &HashSet<String>, blocked_substrings: &[&str], hard_stop: Option<u8>, max_attempts: usize) -> Result<String,String> {
+        let blocklist: HashSet<String> = blocklist.iter().map(|b| b.to_lowercase()).collect();
+        let blocked_substrings: Vec<String> = blocked_substrings.iter().map(|b| b.to_lowercase()).collect();
+        for _ in 0..max_attempts {
+            let name_string = self.build_random_name(hard_stop)?;
+            if !blocklist.contains(&name_string) && !blocked_substrings.iter().any(|b| name_string.contains(b.as_str())) {
+                return Ok(name_string);
+            }
+        }
+        Err(format!("No name free of blocked strings was produced within {max_attempts} attempts"))
+    }
+    /// Behaves like `build_random_name`, but a name that is a member of `seen` is rejected and another name is generated in its place, so that names read while training are not
+    /// regurgitated. Comparisons ignore case. Produces an Err if no novel name is produced within `max_attempts` names, or if generation itself produces an Err.
+    /// 
+    /// Weights do not retain the names that were read, so building `seen` (e.g. from the same list the experiment was trained on) is the responsibility of the caller.
+    /// 
+    /// ## Parameters
+    /// * seen: Names that must never be produced, typically the training set
+    /// * max_attempts: The number of names generated before giving up
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    pub fn build_novel_name(&self, seen: &HashSet<String>, max_attempts: u32, hard_stop: Option<u8>) -> Result<String,String> {
+        let seen: HashSet<String> = seen.iter().map(|name| name.to_lowercase()).collect();
+        for _ in 0..max_attempts {
+            let name_string = self.build_random_name(hard_stop)?;
+            if !seen.contains(&name_string) {
+                return Ok(name_string);
+            }
+        }
+        Err(format!("No name outside of the seen names was produced within {max_attempts} attempts"))
+    }
+    /// Estimates the mean length of names produced by `build_random_name` with default settings (a `hard_stop` of `16`). The estimate is the mean of a short Monte Carlo run of
+    /// `EXPECTED_LENGTH_SAMPLES` names, so it varies slightly between calls. Comparing it against the lengths of the names read can reveal when the termination model disagrees with the corpus.
+    /// 
+    /// A name that fails part way through counts with the length it reached. Produces `0.0` for an experiment that has not read any samples.
+    pub fn expected_generated_length(&self) -> f64 {
+        if self.name_sizes.1 == 0 {return 0.0;}
+        let mut total_length = 0usize;
+        let mut name_string = String::new();
+        for _ in 0..Self::EXPECTED_LENGTH_SAMPLES {
+            name_string.clear();
+            let _ = self.build_name_into(&mut name_string, None, SamplingStrategy::Proportional, &self.name_sizes, &[], None);
+            total_length += name_string.chars().count();
+        }
+        total_length as f64 / Self::EXPECTED_LENGTH_SAMPLES as f64
+    }
+    /// Generates `count` names with `build_random_name` (`SamplingStrategy::Proportional`) and summarises how varied they are. See `DiversityReport`.
+    /// 
+    /// ## Parameters
+    /// * count: The number of names to generate for the report
+    pub fn sample_diversity(&self, count: usize) -> Result<DiversityReport, String> {
+        let mut names: Vec<String> = Vec::with_capacity(count);
+        for _ in 0..count {
+            names.push(self.build_random_name(None)?);
+        }
+        Ok(DiversityReport::from_names(&names))
+    }
+    /// Closes the reinforcement loop (see Recommended usage). Generates `count` names with `build_random_name` and passes each to `judge` as soon as it is generated.
+    /// Names accepted by `judge` are read as positive samples and rejected names are read as negative samples, so later names in the batch already benefit from the feedback.
+    /// Produces the accepted names in the order they were generated.
+    /// 
+    /// Empty names are neither judged nor read. An Err is produced if generation or reading produces an Err, and names judged before it remain read.
+    /// 
+    /// ## Parameters
+    /// * count: The number of names generated
+    /// * judge: Decides whether a name is valid
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    pub fn generate_and_reinforce(&mut self, count: usize, mut judge: impl FnMut(&str) -> bool, hard_stop: Option<u8>) -> Result<Vec<String>, String> {
+        let mut accepted: Vec<String> = vec![];
+        let mut text: Vec<Option<char>> = Vec::with_capacity(Self::MAX_NAME_LENGTH + 1);
+        for _ in 0..count {
+            let name = self.build_random_name(hard_stop)?;
+            if name.is_empty() {continue;}
+            text.clear();
+            text.extend(name.chars().map(Some));
+            text.push(None);
+            if judge(&name) {
+                self.read_sample(&text, TestType::Pos, None)?;
+                accepted.push(name);
+            } else {
+                self.read_sample(&text, TestType::Neg, None)?;
+            }
+        }
+        Ok(accepted)
+    }
+}
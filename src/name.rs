@@ -1,10 +1,14 @@
+use std::collections::HashSet;
+
+use crate::ValidChar;
+
 #[derive(Debug, Clone, Copy)]
 
 /// A tagged enum with to flag if the name is left or right biased in terms of null padding
 pub enum PaddingBias {
     /// The array of characters is padded with None characters to the farthest right
     Left,
-    /// The array of characters is padded with None characters to teh farthest left
+    /// The array of characters is padded with None characters to the farthest left
     Right
 }
 
@@ -26,7 +30,14 @@ pub struct Name<const N: usize>{
 }
 
 impl<const N: usize> Name<N> {
+    /// Written in `text` to end a name early. A terminator is stored as `None`, the same as padding. See `ValidChar::TERMINATOR`
+    pub const TERMINATOR: char = ValidChar::TERMINATOR;
     /// Create a new name using string slices and optional string slices. 
+    /// 
+    /// Any `Name::TERMINATOR` within `text` is stored as `None`. The casing of `text` is preserved so that capitalization patterns can be learned (see `NameExperiments::capitalization_templates`).
+    /// Reading a sample is case insensitive.
+    /// 
+    /// Panics if `text` is longer than `N - 1` characters or `gender_ident` is longer than 16 characters. See `try_new`.
     pub fn new(
         text: &str,
         gender_ident: &str,
@@ -36,41 +47,54 @@ impl<const N: usize> Name<N> {
         sentiment_label: Option<&str>,
         family_label: Option<&str>,
     ) -> Self {
-        if text.len() > N-1 {panic!("Name too long")}
-        if gender_ident.len() > 16 {panic!("Gender identity too long")}
+        Self::try_new(text, gender_ident, padding_bias, major_culture_label, minor_culture_label, sentiment_label, family_label).unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Behaves like `new`, but produces an Err naming the field and its limit rather than panicking if `text` is longer than `N - 1` characters
+    /// or `gender_ident` is longer than 16 characters. Lengths are counted in characters rather than bytes, so multibyte characters are not counted more than once.
+    pub fn try_new(
+        text: &str,
+        gender_ident: &str,
+        padding_bias: PaddingBias,
+        major_culture_label: Option<&str>,
+        minor_culture_label: Option<&str>,
+        sentiment_label: Option<&str>,
+        family_label: Option<&str>,
+    ) -> Result<Self, String> {
+        let char_count = text.chars().count();
+        let text_limit = N.saturating_sub(1);
+        if char_count > text_limit {
+            return Err(format!("Name too long: text has {char_count} characters, but the limit is {text_limit}"));
+        }
+        let gender_count = gender_ident.chars().count();
+        if gender_count > 16 {
+            return Err(format!("Gender identity too long: gender_ident has {gender_count} characters, but the limit is 16"));
+        }
         let mut chars = [None; N];
-        text.chars().into_iter().enumerate().for_each(|(i, c)| {
+        text.chars().enumerate().for_each(|(i, c)| {
+            let c = if ValidChar::is_terminator(c) {None} else {Some(c)};
             match padding_bias {
                 PaddingBias::Left => {
-                    if i<N {
-                        chars[i] = Some(c.to_ascii_lowercase());
-                    }
+                    chars[i] = c;
                 },
                 PaddingBias::Right => {
-                    if i<N {
-                        chars[N-i-1] = Some(c.to_ascii_lowercase());
-                    }
+                    chars[N-char_count+i] = c;
                 }
             }
         });
-        match padding_bias{
-            PaddingBias::Left => chars[text.len()] = Some('_'),
-            PaddingBias::Right => chars[N-text.len()-1] = Some('_'),
-        }
         let mut gen_chars = [None; 16];
-        gender_ident.chars().into_iter().enumerate().for_each(|(i, c)| {
+        gender_ident.chars().enumerate().for_each(|(i, c)| {
             if i<16 {
                 gen_chars[i] = Some(c);
             }
         });
-        Self {
-            text: str_to_char_arr(text),
+        Ok(Self {
+            text: chars,
             gender_identity: str_to_char_arr(gender_ident),
-            major_culture_label: major_culture_label.map(|s| str_to_char_arr(s)),
-            minor_culture_label: minor_culture_label.map(|s| str_to_char_arr(s)),
-            sentiment_label: sentiment_label.map(|s| str_to_char_arr(s)),
-            family_label: family_label.map(|s| str_to_char_arr(s)),
-        }
+            major_culture_label: major_culture_label.map(str_to_char_arr),
+            minor_culture_label: minor_culture_label.map(str_to_char_arr),
+            sentiment_label: sentiment_label.map(str_to_char_arr),
+            family_label: family_label.map(str_to_char_arr),
+        })
     }
     /// Uses an array slice of string slices to create a batch of names all belonging within one label grouping.
     pub fn new_from_batch(
@@ -82,16 +106,42 @@ impl<const N: usize> Name<N> {
         sentiment_label: Option<&str>,
         family_label: Option<&str>,
     ) -> Vec<Self> {
-        texts.into_iter().map(|&text| {
+        texts.iter().map(|&text| {
             Self::new(text, gender_ident, padding_bias, major_culture_label, minor_culture_label, sentiment_label, family_label)
         }).collect()
     }
+    /// Behaves like `new_from_batch`, but drops duplicate names so that repeated names do not over-strengthen weights when read. Produces the names in the order they were first seen,
+    /// paired with the number of duplicates that were dropped.
+    /// 
+    /// Each text is trimmed of leading and trailing whitespace, and texts are compared ignoring case (e.g. "Grukthar" and " grukthar" are duplicates). The first text seen is kept
+    /// with its casing, so that capitalization patterns can still be learned. Blank texts are skipped and are not counted as duplicates.
+    pub fn new_from_batch_deduped(
+        texts: &[&str],
+        gender_ident: &str,
+        padding_bias: PaddingBias,
+        major_culture_label: Option<&str>,
+        minor_culture_label: Option<&str>,
+        sentiment_label: Option<&str>,
+        family_label: Option<&str>,
+    ) -> (Vec<Self>, usize) {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut duplicates = 0usize;
+        let mut names: Vec<Self> = Vec::with_capacity(texts.len());
+        for text in texts.iter().map(|text| text.trim()).filter(|text| !text.is_empty()) {
+            if seen.insert(text.to_lowercase()) {
+                names.push(Self::new(text, gender_ident, padding_bias, major_culture_label, minor_culture_label, sentiment_label, family_label));
+            } else {
+                duplicates += 1;
+            }
+        }
+        (names, duplicates)
+    }
 }
 
 
 fn str_to_char_arr<const N: usize>(text:&str) -> [Option<char>; N] {
     let mut chars = [None; N];
-    text.chars().into_iter().enumerate().for_each(|(i, c)| {
+    text.chars().enumerate().for_each(|(i, c)| {
         if i<N {
             chars[i] = Some(c);
         }
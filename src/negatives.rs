@@ -0,0 +1,51 @@
+use std::collections::BTreeMap;
+
+use crate::{Counter, NameExperiments, ValidChar, WeightStore};
+
+const VALID_CHAR_COUNT: usize = ValidChar::VARIANTCOUNT as usize;
+
+/// Finalized negative penalties, keyed by the variant indices of a context. Only contexts with negative observations are held
+pub(crate) type NegativePenalties<const N: usize> = BTreeMap<[u8; N], [f64; VALID_CHAR_COUNT]>;
+
+impl<const N: usize, S: WeightStore<{ValidChar::VARIANTCOUNT as usize}>> NameExperiments<N, S> {
+    /// The smallest factor a finalized negative penalty will scale a character's probability by
+    pub const MIN_NEGATIVE_PENALTY: f64 = 0.05;
+
+    /// Converts the raw negative character observations into a steadier penalty per context, which generation then uses in place of the raw inverse-frequency factor.
+    ///
+    /// A character observed `s` times amongst `n` negative observations of a context is penalized by `1 - s/(n + count_chars)`, clamped to at least `MIN_NEGATIVE_PENALTY`.
+    /// The pseudo-count keeps a context with only a few negative observations from suppressing characters harshly, and characters never observed in a negative sample are not penalized.
+    /// Character type observations are not affected, and `neg_easing_scale` has no effect on finalized penalties.
+    ///
+    /// Intended to be called once negative training is complete. Reading another negative sample discards the finalized penalties and returns to the raw factor.
+    pub fn finalize_negatives(&mut self) {
+        let mut penalties: NegativePenalties<N> = BTreeMap::new();
+        let contexts = VALID_CHAR_COUNT.pow(N as u32);
+        for index in 0..contexts {
+            let mut context = [ValidChar::null; N];
+            let mut remainder = index;
+            for ch in context.iter_mut() {
+                *ch = ValidChar::ALLCHARS[remainder % VALID_CHAR_COUNT];
+                remainder /= VALID_CHAR_COUNT;
+            }
+            let (Ok(row), Ok(sum)) = (self.negative_char_samples.get_row(&context), self.negative_char_samples.sum(&context)) else {continue};
+            if sum == 0 {continue;}
+            let mut penalty = [1.0; VALID_CHAR_COUNT];
+            for (p, &observations) in penalty.iter_mut().zip(row.iter()) {
+                *p = (1.0 - observations.to_usize() as f64 / (sum + VALID_CHAR_COUNT) as f64).max(Self::MIN_NEGATIVE_PENALTY);
+            }
+            penalties.insert(context.map(|ch| ch as u8), penalty);
+        }
+        self.negative_penalties = Some(penalties);
+    }
+    /// Checks if `finalize_negatives` has been called since the last negative sample was read
+    pub fn negatives_finalized(&self) -> bool {
+        self.negative_penalties.is_some()
+    }
+    /// The finalized penalty of each valid character following `char_seq`, if negatives have been finalized. Only the first `N` characters of `char_seq` are read.
+    pub(crate) fn finalized_penalties(&self, char_seq: &[ValidChar]) -> Option<[f64; VALID_CHAR_COUNT]> {
+        let penalties = self.negative_penalties.as_ref()?;
+        let key: [u8; N] = std::array::from_fn(|i| char_seq[i] as u8);
+        Some(penalties.get(&key).copied().unwrap_or([1.0; VALID_CHAR_COUNT]))
+    }
+}
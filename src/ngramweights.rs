@@ -1,33 +1,114 @@
 use std::fmt::Debug;
 
-#[derive(Debug, Clone)]
-pub struct NGramWeights<const N: usize, const V: usize> {
-    pub weights: Vec<[u8;V]>,
-    pub sum: Vec<usize>,
+/// Storage for n-gram observations. For every sequence of `N` values (a row) a store counts how many times each of `V` values was observed following it, along with the total of those counts.
+///
+/// `NameExperiments` keeps its character weights in a `WeightStore`, so that dense, sparse or memory-mapped storage can be chosen without forking the experiment.
+/// The default store is a dense `Vec` holding a row for every possible sequence. It is fast to read, but allocates all `V^N` rows up front.
+pub trait WeightStore<const V: usize> {
+    /// The type each observation is counted in
+    type Counter: Counter;
+    /// Create an empty store. Panics if the rows of the store can not be indexed
+    fn new() -> Self;
+    /// Create an empty store, producing an Err rather than panicking if the rows of the store can not be indexed. Defaults to `new`
+    fn try_new() -> Result<Self, String> where Self: Sized {
+        Ok(Self::new())
+    }
+    /// Create an empty store configured like this one, e.g. holding the same values, so that a store can be added alongside it without repeating its configuration.
+    /// Defaults to `try_new`, stores that are configured after they are created should copy their configuration
+    fn empty_like(&self) -> Result<Self, String> where Self: Sized {
+        Self::try_new()
+    }
+    /// The observations following `char_seq`. Only the first `N` values are read. Produces an Err if fewer than `N` values are given
+    fn get_row<T>(&self, char_seq: &[T]) -> Result<[Self::Counter; V], String>
+        where usize: From<T>, T: Clone + Copy + Debug;
+    /// Mutable access to the observations following `char_seq`. Changes made through the row are not reflected in `sum`. Produces an Err if fewer than `N` values are given
+    fn get_mut_row<T>(&mut self, char_seq: &[T]) -> Result<&mut [Self::Counter; V], String>
+        where usize: From<T>, T: Clone + Copy + Debug;
+    /// Records one observation of `following_char` after `sequence`. Produces an Err if fewer than `N` values are given or if the observation can not be counted
+    fn add<T>(&mut self, sequence: &[T], following_char: &T) -> Result<(), String>
+        where usize: From<T>, T: Clone + Copy + Debug;
+    /// The total number of observations following `char_seq`. Produces an Err if fewer than `N` values are given
+    fn sum<T>(&self, char_seq: &[T]) -> Result<usize, String>
+        where usize: From<T>, T: Clone + Copy + Debug;
+    /// Checks if observations of `value` can be held. A value that can not be held is never generated. Defaults to every value
+    fn contains(&self, _value: usize) -> bool {
+        true
+    }
+    /// Forgets every observation, leaving the store as empty as when it was created. Defaults to replacing the store with `new`, stores that can reuse their allocations should do so
+    fn reset(&mut self) where Self: Sized {
+        *self = Self::new();
+    }
+    /// Scales every count by `factor` (above `0.0` and at most `1.0`), so that older observations fade as new ones are added. A count above one is never rounded down to zero,
+    /// see `NameExperiments::decay`. Defaults to an Err for stores that can not decay their counts
+    fn decay(&mut self, _factor: f64) -> Result<(), String> {
+        Err("This weight store can not decay its observations".to_string())
+    }
 }
 
-impl<const N: usize, const V: usize> NGramWeights<N, V>
-{
-    pub fn new() -> Self {
-        if V.checked_pow(N as u32).is_none() {
-            panic!("Number of {} ngrams picked will result in overflow",N);
-        }
-        let mut weights = Vec::with_capacity(V.pow(N as u32));
-        for _i in 0..(V.pow(N as u32)) {weights.push([0u8;V]);}
-        let mut sum = Vec::with_capacity(V.pow(N as u32));
-        for _i in 0..(V.pow(N as u32)) {sum.push(0);}
-        NGramWeights {
-            weights: weights,
-            sum: sum,
+/// The type a single n-gram observation is counted in. Adding to a count that has reached `Counter::MAX` produces an Err.
+///
+/// Implemented for `u8`, `u16` and `u32`. A wider counter holds the observations of a larger corpus, but every row of weights takes proportionally more memory.
+pub trait Counter: Copy + Debug + Default + Eq {
+    /// The largest count that can be held
+    const MAX: Self;
+    /// Adds one to the count. Produces `None` if the count is already `Counter::MAX`
+    fn checked_increment(self) -> Option<Self>;
+    /// The count as a `usize`
+    fn to_usize(self) -> usize;
+    /// The count holding `value`. Produces `None` if `value` is above `Counter::MAX`
+    fn from_usize(value: usize) -> Option<Self>;
+}
+
+macro_rules! impl_counter {
+    ($($t:ty),*) => {$(
+        impl Counter for $t {
+            const MAX: Self = <$t>::MAX;
+            fn checked_increment(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+            fn to_usize(self) -> usize {
+                self as usize
+            }
+            fn from_usize(value: usize) -> Option<Self> {
+                Self::try_from(value).ok()
+            }
         }
+    )*};
+}
+
+impl_counter!(u8, u16, u32);
+
+/// `count` scaled by `factor` (at most `1.0`) and rounded to the nearest count, except that a count above one never rounds down to zero. Repeated decay never increases a count,
+/// and a count of one is only forgotten once `factor` is below `0.5`
+pub(crate) fn decayed_count(count: usize, factor: f64) -> usize {
+    let decayed = (count as f64 * factor).round() as usize;
+    if count > 1 {decayed.max(1)} else {decayed}
+}
+
+/// Checks that `factor` can be used to decay observations
+pub(crate) fn check_decay_factor(factor: f64) -> Result<(), String> {
+    if factor > 0.0 && factor <= 1.0 {
+        Ok(())
+    } else {
+        Err(format!("A decay factor must be above 0.0 and at most 1.0. Found {factor}"))
     }
+}
+
+/// The dense `WeightStore`. `C` is the `Counter` every observation is counted in, defaulting to `u8`
+#[derive(Debug, Clone)]
+pub struct NGramWeights<const N: usize, const V: usize, C = u8> {
+    pub(crate) weights: Vec<[C;V]>,
+    pub(crate) sum: Vec<usize>,
+}
+
+impl<const N: usize, const V: usize, C: Counter> NGramWeights<N, V, C>
+{
     fn get_row_index<T>(&self, char_seq: &[T]) -> Result<usize,String>
         where usize: From<T>, T: Clone + Copy + Debug
     {
         if char_seq.len() < N {return Err("Not enough characters given to determine row".to_string())}
         let mut index = 0usize;
-        for i in 0..N {
-            let char = char_seq[i as usize];
+        for (i, &char) in char_seq.iter().enumerate().take(N) {
             index += (V.pow(i as u32)) * (usize::from(char));
         }
         #[cfg(test)]
@@ -36,24 +117,31 @@ impl<const N: usize, const V: usize> NGramWeights<N, V>
         }
         Ok(index)
     }
-    pub fn get_row<T>(&self, char_seq: &[T]) -> Result<[u8;V],String> 
-        where usize: From<T>, T: Clone + Copy + Debug
-    {
-        let index = self.get_row_index(char_seq)?;
-        Ok(self.weights[index])
+    /// The inverse of `get_row_index`. Produces the sequence of variant indices that maps to a row
+    pub fn get_row_sequence(&self, index: usize) -> [usize; N] {
+        let mut sequence = [0usize; N];
+        let mut remainder = index;
+        for s in sequence.iter_mut() {
+            *s = remainder % V;
+            remainder /= V;
+        }
+        sequence
     }
-    pub fn get_row_and_sum<T>(&self, char_seq: &[T]) -> Result<([u8;V], usize),String> 
+    /// The observations following `char_seq` along with their total. Only the first `N` values are read. Produces an Err if fewer than `N` values are given
+    pub fn get_row_and_sum<T>(&self, char_seq: &[T]) -> Result<([C;V], usize),String> 
         where usize: From<T>, T: Clone + Copy + Debug
     {
         let index = self.get_row_index(char_seq)?;
         Ok((self.weights[index], self.sum[index]))
     }
-    pub fn get_mut_row_and_sum<T>(&mut self, char_seq:&[T]) -> Result<(&mut [u8;V], &mut usize),String> 
+    /// Mutable access to the observations following `char_seq` and their total. Produces an Err if fewer than `N` values are given
+    pub fn get_mut_row_and_sum<T>(&mut self, char_seq:&[T]) -> Result<(&mut [C;V], &mut usize),String> 
         where usize: From<T>, T: Clone + Copy + Debug
     {
         let index = self.get_row_index(char_seq)?;
         Ok((self.weights.get_mut(index).unwrap(), self.sum.get_mut(index).unwrap()))
     }
+    /// Records one observation of `following_char` after `sequence`. Produces an Err, leaving the row unchanged, if the observation would exceed `Counter::MAX`
     pub fn add_to_weights<T>(&mut self, sequence: &[T], following_char: &T) -> Result<(),String>
         where usize: From<T>,
         T: Clone + Copy + Debug
@@ -61,24 +149,89 @@ impl<const N: usize, const V: usize> NGramWeights<N, V>
         if sequence.len() < (N) {return Err("Not enough characters in input character sequence".to_string())}
         let (row, sum) = self.get_mut_row_and_sum(sequence).expect("Previous check should have gaurded against character input length errors");
         let column = usize::from(*following_char);
-        row[column] = row[column].checked_add(1).ok_or("Weights max capacity reached")?;
+        row[column] = row[column].checked_increment().ok_or("Weights max capacity reached")?;
         *sum = sum.checked_add(1).ok_or("Max ngram experiments reached")?;
         Ok(())
     }
-    pub fn apply_easing(&mut self, numerator: u8, demoninator: u8) -> Result<(),String> {
-        self.weights.iter_mut().enumerate().for_each(|(index, row)| {
-            let mut fraction = 1u8;
-            while (self.sum.get(index).unwrap()/fraction as usize).checked_add((numerator as usize * V)/demoninator as usize).is_none() {fraction += 1;}
-            while row.iter().any(|&w| u8::try_from(
-                ((w as usize/fraction as usize) + numerator as usize)/(demoninator as usize)
-            ).is_err()) {fraction += 1;}
-            let sum = self.sum[index];
-            self.sum.get_mut(index).replace(&mut ((sum / fraction as usize) + (f64::round((numerator as f64 / demoninator as f64) * V as f64)) as usize));
-            for j in 0..row.len() {
-                let w = row[j];
-                row[j] = (row[j] / fraction) + (f64::round(numerator as f64 / demoninator as f64) as u8)
+    /// Additive smoothing by `numerator / denominator` pseudo-observations of every value in every row. A fractional pseudo-count can not be held by a `Counter`,
+    /// so every count `w` becomes `w * denominator + numerator` instead, which leaves each row with the same proportions as adding the fraction would.
+    /// A row whose largest count would no longer fit within `Counter::MAX` is divided down until it does, rounding down, so close counts may tie and the pseudo-count may round away.
+    /// The relative ordering of the counts within a row is never reversed, and sums are recomputed from the smoothed counts.
+    ///
+    /// Easing at probability computation time (see `NameExperiments::generate_probability_distribution`) is exact and leaves the weights untouched, and should be preferred.
+    /// Produces an Err if `denominator` is `0`.
+    pub fn apply_easing(&mut self, numerator: u8, denominator: u8) -> Result<(),String> {
+        if denominator == 0 {
+            return Err("The denominator of easing must be above 0".to_string());
+        }
+        let (numerator, denominator) = (numerator as usize, denominator as usize);
+        for (row, sum) in self.weights.iter_mut().zip(self.sum.iter_mut()) {
+            let largest = row.iter().map(|weight| weight.to_usize()).max().unwrap_or(0) * denominator + numerator;
+            // The smallest divisor that brings the largest smoothed count within the counter
+            let divisor = largest.div_ceil(C::MAX.to_usize()).max(1);
+            for weight in row.iter_mut() {
+                *weight = C::from_usize((weight.to_usize() * denominator + numerator) / divisor).ok_or("A smoothed weight did not fit within its counter")?;
+            }
+            *sum = row.iter().map(|weight| weight.to_usize()).sum();
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize, const V: usize, C: Counter> WeightStore<V> for NGramWeights<N, V, C> {
+    type Counter = C;
+
+    fn new() -> Self {
+        Self::try_new().unwrap_or_else(|e| panic!("{e}"))
+    }
+    fn try_new() -> Result<Self, String> {
+        let rows = V.checked_pow(N as u32).ok_or(format!("Number of {N} ngrams picked will result in overflow"))?;
+        let mut weights = Vec::with_capacity(rows);
+        for _i in 0..rows {weights.push([C::default();V]);}
+        let mut sum = Vec::with_capacity(rows);
+        for _i in 0..rows {sum.push(0);}
+        Ok(NGramWeights {
+            weights,
+            sum,
+        })
+    }
+    /// Zeroes every row and sum, reusing the existing allocations
+    fn reset(&mut self) {
+        self.weights.fill([C::default(); V]);
+        self.sum.fill(0);
+    }
+    fn decay(&mut self, factor: f64) -> Result<(), String> {
+        check_decay_factor(factor)?;
+        for (row, sum) in self.weights.iter_mut().zip(self.sum.iter_mut()) {
+            if *sum == 0 {continue;}
+            for weight in row.iter_mut() {
+                *weight = C::from_usize(decayed_count(weight.to_usize(), factor)).unwrap_or(*weight);
             }
-        });
+            *sum = row.iter().map(|weight| weight.to_usize()).sum();
+        }
         Ok(())
     }
+    fn get_row<T>(&self, char_seq: &[T]) -> Result<[Self::Counter; V], String>
+        where usize: From<T>, T: Clone + Copy + Debug
+    {
+        let index = self.get_row_index(char_seq)?;
+        Ok(self.weights[index])
+    }
+    fn get_mut_row<T>(&mut self, char_seq: &[T]) -> Result<&mut [Self::Counter; V], String>
+        where usize: From<T>, T: Clone + Copy + Debug
+    {
+        let (row, _) = self.get_mut_row_and_sum(char_seq)?;
+        Ok(row)
+    }
+    fn add<T>(&mut self, sequence: &[T], following_char: &T) -> Result<(), String>
+        where usize: From<T>, T: Clone + Copy + Debug
+    {
+        self.add_to_weights(sequence, following_char)
+    }
+    fn sum<T>(&self, char_seq: &[T]) -> Result<usize, String>
+        where usize: From<T>, T: Clone + Copy + Debug
+    {
+        let index = self.get_row_index(char_seq)?;
+        Ok(self.sum[index])
+    }
 }
@@ -0,0 +1,33 @@
+use rand::distributions::Distribution;
+
+use crate::{InferenceModel, NameExperiments, SamplingStrategy};
+
+/// Generates names like `NameExperiments::build_random_name` with default settings (a `hard_stop` of `16`), drawing randomness from the given `rand` generator.
+/// A generator seeded through `NameExperiments::seed` is not used.
+///
+/// A `Distribution` can not produce an Err. If generation fails part way, the partial name is produced instead.
+///
+/// ```
+/// use rand::Rng;
+/// use random_person_name::NameExperiments;
+///
+/// let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+/// name_guess_experiments.train_positive_stream("Morgash\nNargul\nSnarlgash".as_bytes()).unwrap();
+/// let names: Vec<String> = rand::thread_rng().sample_iter(&name_guess_experiments).take(5).collect();
+/// assert_eq!(names.len(), 5);
+/// ```
+impl<const N: usize> Distribution<String> for NameExperiments<N> {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> String {
+        let mut picker = fastrand::Rng::with_seed(rng.gen());
+        let mut name_string = String::new();
+        let _ = self.build_name_into(&mut name_string, None, SamplingStrategy::Proportional, &self.name_sizes, &[], Some(&mut picker));
+        name_string
+    }
+}
+
+/// See the `Distribution` implementation of `NameExperiments`
+impl<const N: usize> Distribution<String> for InferenceModel<'_, N> {
+    fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> String {
+        self.experiments.sample(rng)
+    }
+}
@@ -0,0 +1,98 @@
+use fastrand::Rng;
+
+/// A source of randomness for picking characters. Implemented for `fastrand::Rng`, which is used whenever no other source is given.
+///
+/// Implement it to draw every pick from the random number generator the rest of an application already uses, and pass it to `NameExperiments::build_random_name_seeded`
+/// or `NameExperiments::guess_next_char_seeded`. For example, an adapter for any `rand::RngCore`:
+///
+#[cfg_attr(feature = "rand", doc = "```")]
+#[cfg_attr(not(feature = "rand"), doc = "```ignore")]
+/// use rand::{RngCore, SeedableRng};
+/// use random_person_name::{NameExperiments, RandomSource};
+///
+/// struct RandAdapter<R>(R);
+///
+/// impl<R: RngCore> RandomSource for RandAdapter<R> {
+///     fn next_f64(&mut self) -> f64 {
+///         // The top 53 bits fill the mantissa of an f64 in [0, 1)
+///         (self.0.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+///     }
+/// }
+///
+/// let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+/// name_guess_experiments.train_positive_stream("Morgash\nNargul\nSnarlgash".as_bytes()).unwrap();
+/// let mut rng = RandAdapter(rand::rngs::StdRng::seed_from_u64(7));
+/// assert!(name_guess_experiments.build_random_name_seeded(None, &mut rng).is_ok());
+/// ```
+pub trait RandomSource {
+    /// Produces a number in the range `[0, 1)`
+    fn next_f64(&mut self) -> f64;
+}
+
+impl RandomSource for Rng {
+    fn next_f64(&mut self) -> f64 {
+        self.f64()
+    }
+}
+
+/// Controls how a character is picked from a probability distribution produced by `NameExperiments::generate_probability_distribution`.
+///
+/// The same trained weights can be sampled in different ways without recomputing anything. `NameExperiments::guess_next_char` and `NameExperiments::build_random_name`
+/// use `SamplingStrategy::Proportional`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum SamplingStrategy {
+    /// Picks a character with a chance proportional to its probability
+    #[default]
+    Proportional,
+    /// Picks uniformly between the `top_k` most probable characters, ignoring how probable each of them is. Characters with a probability of `0.0` are never picked
+    Uniform(usize),
+    /// Always picks the most probable character. Ties are broken by the order of `ValidChar::ALLCHARS`
+    Greedy,
+}
+
+impl SamplingStrategy {
+    /// Picks an index into `probabilities` using `rng`. `sum_of_probabilities` is the sum of all entries of `probabilities`.
+    /// An entry with a probability of `0.0` is never picked, so an Err is produced if every entry is `0.0`.
+    pub(crate) fn pick(&self, probabilities: &[f64], sum_of_probabilities: f64, rng: &mut dyn RandomSource) -> Result<usize, String> {
+        match *self {
+            SamplingStrategy::Proportional => {
+                // A pick from a sum of 0.0 would always land on the first entry
+                if sum_of_probabilities <= 0.0 || !sum_of_probabilities.is_normal() {
+                    return Err(format!("Can not pick from a probability distribution that sums to {sum_of_probabilities}"));
+                }
+                let mut random_pick = rng.next_f64() * sum_of_probabilities;
+                let pick_start = random_pick;
+                probabilities.iter().enumerate().find_map(|(i, &p)| {
+                    if p > 0.0 && p >= random_pick {Some(i)} else {
+                        random_pick -= p;
+                        None
+                    }
+                }).ok_or(format!("Random pick failed to pick a value. pick:{pick_start}, sum_of_probabilities: {sum_of_probabilities}"))
+            },
+            SamplingStrategy::Uniform(top_k) => {
+                if top_k == 0 {
+                    return Err("Uniform sampling requires top_k of at least 1".to_string());
+                }
+                let mut candidates: Vec<usize> = (0..probabilities.len()).filter(|&i| probabilities[i] > 0.0).collect();
+                // A stable sort keeps ties in the order of ValidChar::ALLCHARS
+                candidates.sort_by(|&a, &b| probabilities[b].total_cmp(&probabilities[a]));
+                candidates.truncate(top_k);
+                if candidates.is_empty() {
+                    return Err(format!("No character has a probability above zero: {probabilities:?}"));
+                }
+                // Guards against a source that produces exactly 1.0
+                let index = ((rng.next_f64() * candidates.len() as f64) as usize).min(candidates.len() - 1);
+                Ok(candidates[index])
+            },
+            SamplingStrategy::Greedy => {
+                probabilities.iter().enumerate()
+                    .fold(None, |best: Option<(usize, f64)>, (i, &p)| match best {
+                        Some((_, best_p)) if best_p >= p => best,
+                        _ => Some((i, p)),
+                    })
+                    .map(|(i, _)| i)
+                    .ok_or("Can not pick from an empty probability distribution".to_string())
+            },
+        }
+    }
+}
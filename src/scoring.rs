@@ -0,0 +1,69 @@
+use crate::{CharType, NameExperiments, ValidChar, WeightStore};
+
+impl<const N: usize, S: WeightStore<{ValidChar::VARIANTCOUNT as usize}>> NameExperiments<N, S> {
+    /// Scores how likely the model is to generate `text`. Produces the natural log of the probability of every character of `text` followed by the terminating null character,
+    /// e.g. to rank candidate names or to drop generated names below a threshold. Higher (closer to `0.0`) is more likely.
+    /// 
+    /// The name is walked the same way `read_sample` reads it: leading `None` values are skipped, the name ends at the next `None` or `ValidChar::TERMINATOR`, and characters
+    /// that are not a `ValidChar` are scored as `ValidChar::null`. Each probability comes from `generate_probability_distribution` with default settings, normalized at every character,
+    /// as in `top_names`. Produces `f64::NEG_INFINITY` if any character can not be generated.
+    /// 
+    /// ## Parameters
+    /// * text: The name to score. Both left padded and right padded text is accepted (see `PaddingBias`)
+    pub fn score_name(&self, text: &[Option<char>]) -> Result<f64, String> {
+        let (log_probability, _) = self.score_with_count(text)?;
+        Ok(log_probability)
+    }
+    /// Measures how well the model predicts a held-out list of names, e.g. to compare models of different `N`. Produces the exponential of the negative mean log-probability
+    /// per character (each name's terminating null included), as scored by `score_name`. Lower is better; a model that predicts every character perfectly scores `1.0`.
+    /// 
+    /// Names are read like `score_name` reads them, so characters that are not a `ValidChar` are scored as `ValidChar::null` rather than producing an Err.
+    /// Produces `f64::INFINITY` if any character can not be generated (e.g. a name ending at a length the model does not end names at) and an Err if `names` is empty.
+    pub fn perplexity(&self, names: &[&[Option<char>]]) -> Result<f64, String> {
+        if names.is_empty() {
+            return Err("At least one name is needed to measure perplexity".to_string());
+        }
+        let mut total_log_probability = 0.0;
+        let mut total_chars = 0usize;
+        for text in names.iter() {
+            let (log_probability, chars) = self.score_with_count(text)?;
+            total_log_probability += log_probability;
+            total_chars += chars;
+        }
+        Ok((-total_log_probability / total_chars as f64).exp())
+    }
+    /// Behind `score_name`. The log-probability is produced with the number of characters scored, the terminating null included
+    fn score_with_count(&self, text: &[Option<char>]) -> Result<(f64, usize), String> {
+        let text_char = |c: &Option<char>| c.filter(|&c| !ValidChar::is_terminator(c));
+        let start = text.iter().position(|c| text_char(c).is_some()).unwrap_or(text.len());
+        let mut char_array = [ValidChar::null; N];
+        let mut char_type_array = [CharType::Null; N];
+        let mut char_window = [ValidChar::null; CharType::WINDOW];
+        let mut letter_position = 0usize;
+        let mut log_probability = 0.0;
+        let mut scored = 0usize;
+        let mut following_chars = text[start..].iter().map_while(text_char)
+            .map(|c| ValidChar::try_from(&c).unwrap_or(ValidChar::null))
+            .chain([ValidChar::null]);
+        for next_char in following_chars.by_ref() {
+            let (probabilities, sum_of_probabilities, _) = self.distribution_with_sizes(
+                &char_array, &char_type_array, letter_position, None, None, None, &self.name_sizes, None
+            )?;
+            let p = probabilities[next_char as usize];
+            scored += 1;
+            // The name can not be generated, so the characters after it are counted without being scored
+            if p <= 0.0 || sum_of_probabilities <= 0.0 {
+                return Ok((f64::NEG_INFINITY, scored + following_chars.count()));
+            }
+            log_probability += (p / sum_of_probabilities).ln();
+            if next_char != ValidChar::syllable_break {letter_position += 1;}
+            char_window.rotate_left(1);
+            char_window[CharType::WINDOW-1] = next_char;
+            char_array.rotate_left(1);
+            char_array[N-1] = next_char;
+            char_type_array.rotate_left(1);
+            char_type_array[N-1] = CharType::try_from(&char_window)?;
+        }
+        Ok((log_probability, scored))
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::ngramweights::NGramWeights;
+use crate::{CharType, GenerationConfig, NameExperiments, SizeDistribution, ValidChar};
+
+/// The rows of a weights matrix, serialized as a sequence of byte sequences
+struct Rows<'a, const V: usize>(&'a [[u8; V]]);
+
+impl<const V: usize> Serialize for Rows<'_, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(|row| row.as_slice()))
+    }
+}
+
+#[derive(Serialize)]
+struct WeightsRef<'a, const V: usize> {
+    weights: Rows<'a, V>,
+    sum: &'a [usize],
+}
+
+#[derive(Deserialize)]
+struct WeightsData {
+    weights: Vec<Vec<u8>>,
+    sum: Vec<usize>,
+}
+
+impl<const N: usize, const V: usize> Serialize for NGramWeights<N, V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        WeightsRef { weights: Rows(&self.weights), sum: &self.sum }.serialize(serializer)
+    }
+}
+
+impl<'de, const N: usize, const V: usize> Deserialize<'de> for NGramWeights<N, V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = WeightsData::deserialize(deserializer)?;
+        let rows = V.checked_pow(N as u32).ok_or(D::Error::custom(format!("Number of {N} ngrams picked will result in overflow")))?;
+        if data.weights.len() != rows || data.sum.len() != rows {
+            return Err(D::Error::custom(format!(
+                "Expected {rows} rows of weights and sums for N={N}. Found {} rows of weights and {} sums", data.weights.len(), data.sum.len()
+            )));
+        }
+        let weights = data.weights.iter()
+            .map(|row| <[u8; V]>::try_from(row.as_slice()).map_err(|_| D::Error::custom(format!("Expected rows of {V} weights. Found a row of {}", row.len()))))
+            .collect::<Result<Vec<[u8; V]>, D::Error>>()?;
+        Ok(NGramWeights { weights, sum: data.sum })
+    }
+}
+
+#[derive(Serialize)]
+struct ExperimentsRef<'a, const N: usize> {
+    positive_char_samples: &'a NGramWeights<N, {ValidChar::VARIANTCOUNT as usize}>,
+    negative_char_samples: &'a NGramWeights<N, {ValidChar::VARIANTCOUNT as usize}>,
+    positive_char_type_samples: &'a NGramWeights<N, {CharType::VARIANTCOUNT}>,
+    negative_char_type_samples: &'a NGramWeights<N, {CharType::VARIANTCOUNT}>,
+    name_sizes: &'a SizeDistribution,
+    labeled_name_sizes: &'a BTreeMap<String, SizeDistribution>,
+    labeled_experiments: &'a BTreeMap<String, NameExperiments<N>>,
+    positive_sample_count: usize,
+    negative_sample_count: usize,
+    capitalization_templates: &'a [(Vec<u8>, usize)],
+    generation_config: &'a GenerationConfig,
+}
+
+#[derive(Deserialize)]
+struct ExperimentsData<const N: usize> {
+    positive_char_samples: NGramWeights<N, {ValidChar::VARIANTCOUNT as usize}>,
+    negative_char_samples: NGramWeights<N, {ValidChar::VARIANTCOUNT as usize}>,
+    positive_char_type_samples: NGramWeights<N, {CharType::VARIANTCOUNT}>,
+    negative_char_type_samples: NGramWeights<N, {CharType::VARIANTCOUNT}>,
+    name_sizes: SizeDistribution,
+    labeled_name_sizes: BTreeMap<String, SizeDistribution>,
+    // Missing from experiments written before labels kept their own weights
+    #[serde(default)]
+    labeled_experiments: BTreeMap<String, NameExperiments<N>>,
+    positive_sample_count: usize,
+    negative_sample_count: usize,
+    capitalization_templates: Vec<(Vec<u8>, usize)>,
+    generation_config: GenerationConfig,
+}
+
+/// Checks that a length histogram can be added to. Its total must match its counts
+fn check_sizes(sizes: &SizeDistribution) -> Result<(), String> {
+    if sizes.0.is_empty() {
+        return Err("A length histogram must have at least one entry".to_string());
+    }
+    let counted: usize = sizes.0.iter().sum();
+    if counted != sizes.1 {
+        return Err(format!("A length histogram counts {counted} names but records a total of {}", sizes.1));
+    }
+    Ok(())
+}
+
+/// Writes the weights (including those kept for each label), length histograms, sample counts, capitalization templates and `GenerationConfig` of the experiment. Available with the `serde` feature.
+///
+/// A generator seeded with `seed` and penalties from `finalize_negatives` are not written.
+impl<const N: usize> Serialize for NameExperiments<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExperimentsRef {
+            positive_char_samples: &self.positive_char_samples,
+            negative_char_samples: &self.negative_char_samples,
+            positive_char_type_samples: &self.positive_char_type_samples,
+            negative_char_type_samples: &self.negative_char_type_samples,
+            name_sizes: &self.name_sizes,
+            labeled_name_sizes: &self.labeled_name_sizes,
+            labeled_experiments: &self.labeled_experiments,
+            positive_sample_count: self.positive_sample_count,
+            negative_sample_count: self.negative_sample_count,
+            capitalization_templates: &self.capitalization_templates,
+            generation_config: &self.generation_config,
+        }.serialize(serializer)
+    }
+}
+
+/// Reads an experiment written by its `Serialize` implementation. Available with the `serde` feature.
+///
+/// Produces an Err rather than panicking if the weights were written for another `N`, or if any weights or length histograms are malformed.
+impl<'de, const N: usize> Deserialize<'de> for NameExperiments<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if N < 2 {
+            return Err(D::Error::custom("N must be at least 2"));
+        }
+        let data = ExperimentsData::<N>::deserialize(deserializer)?;
+        check_sizes(&data.name_sizes).map_err(D::Error::custom)?;
+        for sizes in data.labeled_name_sizes.values() {
+            check_sizes(sizes).map_err(D::Error::custom)?;
+        }
+        Ok(NameExperiments {
+            positive_char_samples: data.positive_char_samples,
+            negative_char_samples: data.negative_char_samples,
+            positive_char_type_samples: data.positive_char_type_samples,
+            negative_char_type_samples: data.negative_char_type_samples,
+            name_sizes: data.name_sizes,
+            labeled_name_sizes: data.labeled_name_sizes,
+            labeled_experiments: data.labeled_experiments,
+            positive_sample_count: data.positive_sample_count,
+            negative_sample_count: data.negative_sample_count,
+            rng: Mutex::new(None),
+            capitalization_templates: data.capitalization_templates,
+            generation_config: data.generation_config,
+            negative_penalties: None,
+            lower_order_counts: OnceLock::new(),
+        })
+    }
+}
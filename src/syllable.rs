@@ -0,0 +1,129 @@
+use crate::{CharType, NameExperiments, SamplingStrategy, SizeDistribution, ValidChar, WeightStore};
+
+const VALID_CHAR_COUNT: usize = ValidChar::VARIANTCOUNT as usize;
+
+/// Checks if `ch` is one of the letters that are always vowels
+fn is_vowel_letter(ch: ValidChar) -> bool {
+    matches!(ch, ValidChar::a | ValidChar::e | ValidChar::i | ValidChar::o | ValidChar::u)
+}
+
+/// Estimates the number of syllables in a name by counting its vowel sounds (nuclei), e.g. `2` for "Grukthar" and `1` for "Grace".
+///
+/// Characters are classified the same way they are while reading a sample (see `CharType`), and each group of adjacent vowels counts once, so "Aurelia" counts "au" as
+/// a single nucleus. A 'y' that stands for a vowel (e.g. "Rhys") is counted, while a 'y' before a vowel (e.g. "Yara") is not. Consonants are never counted, so a leading cluster
+/// (e.g. "Str" in "Strom") adds nothing. Each part of a compound name (e.g. "Iron maul") is counted separately.
+///
+/// A silent 'e' at the end of a part (e.g. "Grace" or "Anne") is not counted unless it is the only vowel of the part, or it ends a consonant followed by "le" (e.g. "Mable").
+/// A vowel directly after a syllable break (see `ValidChar::syllable_break`) always starts a new syllable.
+///
+/// > Note: This is a heuristic over spelling rather than pronunciation. Vowels that are pronounced apart (e.g. the "oe" of "Chloe") are counted once.
+///
+/// `text` is read the same way as `NameExperiments::read_positive_sample` reads it: leading `None` values are skipped and counting stops at the first `None` after the text.
+/// Characters that do not map to a `ValidChar` are read as a break between parts.
+pub fn count_syllables(text: &[Option<char>]) -> usize {
+    let start = text.iter().position(|c| c.is_some_and(|c| !ValidChar::is_terminator(c))).unwrap_or(text.len());
+    let chars: Vec<ValidChar> = text[start..].iter()
+        .map_while(|c| c.filter(|&c| !ValidChar::is_terminator(c)))
+        .map(|c| ValidChar::try_from(&c).unwrap_or(ValidChar::null))
+        .collect();
+    // Classifying a character never fails, as every window holds at least one character
+    let Ok(char_types) = CharType::classify_all(&chars) else {return 0};
+    let ends_part = |i: usize| chars.get(i).is_none_or(|&ch| matches!(CharType::default_for(ch), CharType::SemiPunctuation | CharType::Null));
+    let mut syllables = 0usize;
+    let mut part_syllables = 0usize;
+    for (i, (&ch, &char_type)) in chars.iter().zip(char_types.iter()).enumerate() {
+        let previous_type = i.checked_sub(1).map(|previous| char_types[previous]);
+        // A 'y' modifying the vowel before it (e.g. "Maya") is a consonant to the vowel after it
+        let after_vowel = match previous_type {
+            Some(CharType::VowelRoot) => true,
+            Some(CharType::VowelModifier) => is_vowel_letter(chars[i - 1]),
+            _ => false,
+        };
+        let starts_nucleus = match char_type {
+            CharType::VowelRoot => !after_vowel,
+            // An 'e' after a single consonant is classified as modifying the vowel before it, but only sounds that way at the end of a part
+            CharType::VowelModifier => previous_type == Some(CharType::SyllableBreak) || (is_vowel_letter(ch) && !after_vowel),
+            CharType::SemiPunctuation | CharType::Null => {
+                part_syllables = 0;
+                false
+            },
+            _ => false,
+        };
+        if !starts_nucleus {continue;}
+        let silent_e = ch == ValidChar::e && ends_part(i + 1) && part_syllables > 0 && previous_type != Some(CharType::SyllableBreak)
+            && i.checked_sub(1).is_some_and(|previous| !is_vowel_letter(chars[previous]))
+            && !(i >= 2 && chars[i - 1] == ValidChar::l && !is_vowel_letter(chars[i - 2]));
+        if silent_e {continue;}
+        syllables += 1;
+        part_syllables += 1;
+    }
+    syllables
+}
+
+impl<const N: usize, S: WeightStore<{ValidChar::VARIANTCOUNT as usize}>> NameExperiments<N, S> {
+    /// Behaves like `build_random_name`, but the name ends once it holds `target` syllables (see `count_syllables`) rather than according to the lengths of the names read.
+    /// After every character the syllables are counted again. Until `target` is reached the null (terminating) character is not picked, unless nothing else can be picked.
+    /// Once it is reached, characters that would start another syllable are not picked and the name ends with at least even odds at every character.
+    /// 
+    /// `GenerationConfig::min_length`, `GenerationConfig::length_percentile_range` and backtracking do not apply. If `hard_stop` is reached first the name built so far is produced,
+    /// with fewer syllables than `target`.
+    /// 
+    /// ## Parameters
+    /// * target: The number of syllables in the name
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    pub fn build_random_name_syllables(&self, target: u8, hard_stop: Option<u8>) -> Result<String,String> {
+        let target = usize::from(target);
+        let hard_stop = usize::from(hard_stop.unwrap_or(16));
+        // Every length is as likely to end a name as not, so that only the syllables decide
+        let neutral_sizes: SizeDistribution = (vec![1], 2);
+        let mut char_array = [ValidChar::null; N];
+        let mut char_type_array = [CharType::Null; N];
+        // The name without syllable breaks, so that syllables are counted the same way as in the name produced
+        let mut text: Vec<Option<char>> = vec![];
+        let mut name_string = String::new();
+        let mut char_count = 0usize;
+        while char_count < hard_stop {
+            let (mut probabilities, _, mut char_window) = self.distribution_with_sizes(
+                &char_array, &char_type_array, char_count, None, None, None, &neutral_sizes, None
+            )?;
+            let continuing: f64 = probabilities[..VALID_CHAR_COUNT - 1].iter().sum();
+            if count_syllables(&text) < target {
+                if continuing > 0.0 {
+                    probabilities[VALID_CHAR_COUNT - 1] = 0.0;
+                }
+            } else {
+                for (i, p) in probabilities.iter_mut().enumerate().take(VALID_CHAR_COUNT - 1) {
+                    if ValidChar::ALLCHARS[i] == ValidChar::syllable_break {continue;}
+                    text.push(Some(char::from(ValidChar::ALLCHARS[i])));
+                    if count_syllables(&text) > target {
+                        *p = 0.0;
+                    }
+                    text.pop();
+                }
+                let continuing: f64 = probabilities[..VALID_CHAR_COUNT - 1].iter().sum();
+                let null = &mut probabilities[VALID_CHAR_COUNT - 1];
+                *null = null.max(continuing);
+            }
+            let sum_of_probabilities: f64 = probabilities.iter().sum();
+            if sum_of_probabilities <= 0.0 || !sum_of_probabilities.is_normal() {
+                return Err(format!("No character can follow {char_array:?}. Every character has a probability of 0.0"));
+            }
+            let picked_from_sum = self.generation_config.apply_temperature(&mut probabilities, sum_of_probabilities)?;
+            let index_pick = self.with_rng(|rng| SamplingStrategy::Proportional.pick(&probabilities, picked_from_sum, rng))?;
+            let next_char = ValidChar::ALLCHARS[index_pick];
+            if next_char == ValidChar::null {break;}
+            char_window[CharType::WINDOW-1] = next_char;
+            if next_char != ValidChar::syllable_break {
+                text.push(Some(char::from(next_char)));
+                name_string.push(char::from(next_char));
+                char_count += 1;
+            }
+            char_array.rotate_left(1);
+            char_array[N-1] = next_char;
+            char_type_array.rotate_left(1);
+            char_type_array[N-1] = CharType::try_from(&char_window)?;
+        }
+        name_string.truncate(self.trimmed_length(&name_string));
+        Ok(name_string)
+    }
+}
@@ -0,0 +1,104 @@
+use fastrand::Rng;
+
+use crate::{CharType, GenerationConfig, NameExperiments, SamplingStrategy, ValidChar};
+
+const ROUNDS: usize = 40;
+const NAMES_PER_ROUND: usize = 50;
+/// Characters drawn on when making random corpora. Includes characters that are not valid and characters that lowercase to more than one character
+const CORPUS_CHARS: &[char] = &['a', 'e', 'g', 'k', 'o', 'r', 'u', 'z', 'Q', '-', '\'', '|', ' ', '_', '7', '\u{130}', '\u{1E9E}', '\u{3A3}'];
+
+fn random_corpus(rng: &mut Rng) -> String {
+    let mut corpus = String::new();
+    for _ in 0..rng.usize(0..12) {
+        for _ in 0..rng.usize(0..20) {
+            corpus.push(CORPUS_CHARS[rng.usize(..CORPUS_CHARS.len())]);
+        }
+        corpus.push('\n');
+    }
+    corpus
+}
+
+fn random_strategy(rng: &mut Rng) -> SamplingStrategy {
+    match rng.u8(0..3) {
+        0 => SamplingStrategy::Proportional,
+        1 => SamplingStrategy::Uniform(rng.usize(0..6)),
+        _ => SamplingStrategy::Greedy,
+    }
+}
+
+fn random_easing(rng: &mut Rng) -> Option<f64> {
+    match rng.u8(0..4) {
+        0 => None,
+        1 => Some(0.0),
+        2 => Some(-rng.f64()),
+        _ => Some(rng.f64() * 4.0),
+    }
+}
+
+/// Trains on random corpora and generates from them with random parameters. Any panic fails the test. Produced names are checked for sanity.
+fn check_generation_is_stable<const N: usize>(rng: &mut Rng) {
+    for _ in 0..ROUNDS {
+        let mut name_guess_experiments: NameExperiments<N> = NameExperiments::new();
+        let mut corpus = random_corpus(rng);
+        if rng.u8(0..4) == 0 {
+            // Enough repetition to saturate the u8 weights
+            corpus = corpus.repeat(300);
+        }
+        let _ = name_guess_experiments.train_positive_stream(corpus.as_bytes());
+        if rng.bool() {
+            let _ = name_guess_experiments.train_negative_stream(random_corpus(rng).as_bytes());
+        }
+        name_guess_experiments.set_generation_config(GenerationConfig {
+            backtrack_threshold: rng.f64() * 0.2,
+            max_backtracks: rng.usize(0..4),
+            trim_trailing_separators: rng.bool(),
+            negative_influence: rng.f64() * 3.0,
+            length_percentile_range: if rng.bool() {None} else {Some((rng.f64() * 0.5, 0.5 + rng.f64() * 0.5))},
+            min_length: if rng.bool() {None} else {Some(rng.u8(0..20))},
+            epsilon: if rng.bool() {0.0} else {rng.f64() * 0.5},
+            temperature: match rng.u8(0..3) {
+                0 => 1.0,
+                1 => rng.f64() * 0.05,
+                _ => rng.f64() * 4.0,
+            },
+            backoff_threshold: if rng.bool() {0} else {rng.usize(1..20)},
+        });
+        for _ in 0..NAMES_PER_ROUND {
+            let hard_stop = rng.u8(0..=32);
+            if let Ok(new_name) = name_guess_experiments.build_random_name_with_strategy(Some(hard_stop), random_strategy(rng)) {
+                assert!(hard_stop == 0 || new_name.len() <= hard_stop as usize, "{new_name} is longer than {hard_stop}");
+                assert!(new_name.chars().all(|c| ValidChar::try_from(&c).is_ok_and(|ch| ch != ValidChar::null && ch != ValidChar::syllable_break)), "{new_name} has invalid characters");
+            }
+            let context_length = rng.usize(0..=N + 1);
+            let char_seq: Vec<ValidChar> = (0..context_length).map(|_| ValidChar::ALLCHARS[rng.usize(..ValidChar::ALLCHARS.len())]).collect();
+            let char_type_seq: Vec<CharType> = char_seq.iter().map(|&ch| CharType::default_for(ch)).collect();
+            let _ = name_guess_experiments.generate_probability_distribution(
+                &char_seq,
+                &char_type_seq,
+                rng.usize(..=300),
+                random_easing(rng),
+                random_easing(rng),
+                Some(rng.bool()),
+                Some(rng.bool())
+            );
+            let _ = name_guess_experiments.guess_next_char_with_strategy(&char_seq, &char_type_seq, rng.usize(..=300), random_strategy(rng));
+        }
+        let _ = name_guess_experiments.build_random_name_resilient(rng.usize(0..3), Some(rng.u8(1..=32)));
+        if let Ok(new_name) = name_guess_experiments.build_random_name_syllables(rng.u8(0..6), Some(rng.u8(0..=32))) {
+            assert!(new_name.chars().all(|c| ValidChar::try_from(&c).is_ok_and(|ch| ch != ValidChar::null && ch != ValidChar::syllable_break)), "{new_name} has invalid characters");
+        }
+        let start_filter = [ValidChar::ALLCHARS[rng.usize(..ValidChar::ALLCHARS.len())]];
+        if let Ok(new_name) = name_guess_experiments.build_random_name_starting_with(Some(&start_filter), Some(rng.u8(0..=32))) {
+            assert!(new_name.is_empty() || new_name.starts_with(char::from(start_filter[0])), "{new_name} does not start with {:?}", start_filter[0]);
+        }
+    }
+}
+
+#[test]
+fn it_never_panics_while_generating_from_random_corpora() {
+    let seed = fastrand::u64(..);
+    println!("seed: {seed}");
+    let mut rng = Rng::with_seed(seed);
+    check_generation_is_stable::<2>(&mut rng);
+    check_generation_is_stable::<3>(&mut rng);
+}
@@ -1,5 +1,9 @@
-use crate::{name::{self, Name}, NameExperiments};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+
+use crate::{name::{self, Name}, NameExperiments, DiversityReport, CharType, ValidChar, SamplingStrategy, ModelBuilder, Warning, GenerationConfig, WeightStore, RandomSource, NGramWeights, AlphabetWeights};
 mod test_input_names;
+mod generation_stability;
 use test_input_names::{INPUT_EUROPEAN_MALE_NAMES, INPUT_GOBLIN_NAMES, INPUT_GREEK_FEMALE_NAMES, INPUT_ORC_NAMES, NOT_NAMES};
 
 // use super::*;
@@ -14,7 +18,7 @@ fn it_makes_a_random_orc_name() {
     );
     let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
     for n in names.iter() {
-        let _ = name_guess_experiments.read_positive_sample(&n.text).unwrap();
+        name_guess_experiments.read_positive_sample(&n.text).unwrap();
     }
     let new_name = name_guess_experiments.build_random_name(Some(16)).unwrap();
     println!("Hello, {}!", new_name);
@@ -30,7 +34,7 @@ fn it_makes_a_random_goblin_name() {
     );
     let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
     for n in names.iter() {
-        let _ = name_guess_experiments.read_positive_sample(&n.text).unwrap();
+        name_guess_experiments.read_positive_sample(&n.text).unwrap();
     }
     let new_name = name_guess_experiments.build_random_name(Some(16)).unwrap();
     println!("Hello, {}!", new_name);
@@ -46,7 +50,7 @@ fn it_makes_a_random_western_male_name() {
     );
     let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
     for n in names.iter() {
-        let _ = name_guess_experiments.read_positive_sample(&n.text).unwrap();
+        name_guess_experiments.read_positive_sample(&n.text).unwrap();
     }
     let not_names: Vec<Name<18>> = Name::new_from_batch(
         NOT_NAMES,
@@ -55,7 +59,7 @@ fn it_makes_a_random_western_male_name() {
         Some("Not"), None, None, None
     );
     for nn in not_names.iter() {
-        let _ = name_guess_experiments.read_negative_sample(&nn.text).unwrap();
+        name_guess_experiments.read_negative_sample(&nn.text).unwrap();
     }
     let mut random_names: Vec<String> = Vec::with_capacity(50);
     for _ in 0..50 {
@@ -67,7 +71,7 @@ fn it_makes_a_random_western_male_name() {
     print!("]");
 }
 
-    #[test]
+#[test]
 fn it_makes_a_random_greek_female_name() {
     let names: Vec<Name<16>> = Name::new_from_batch(
         INPUT_GREEK_FEMALE_NAMES,
@@ -77,7 +81,7 @@ fn it_makes_a_random_greek_female_name() {
     );
     let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
     for n in names.iter() {
-        let _ = name_guess_experiments.read_positive_sample(&n.text).unwrap();
+        name_guess_experiments.read_positive_sample(&n.text).unwrap();
     }
     let not_names: Vec<Name<18>> = Name::new_from_batch(
         NOT_NAMES,
@@ -86,7 +90,7 @@ fn it_makes_a_random_greek_female_name() {
         Some("Not"), None, None, None
     );
     for nn in not_names.iter() {
-        let _ = name_guess_experiments.read_negative_sample(&nn.text).unwrap();
+        name_guess_experiments.read_negative_sample(&nn.text).unwrap();
     }
     let mut random_names: Vec<String> = Vec::with_capacity(50);
     for _ in 0..50 {
@@ -126,10 +130,10 @@ fn it_makes_a_random_generic_male_name() {
     );
     let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
     for n in names1.iter().chain(names2.iter()).chain(names3.iter()) {
-        let _ = name_guess_experiments.read_positive_sample(&n.text).unwrap();
+        name_guess_experiments.read_positive_sample(&n.text).unwrap();
     }
     for nn in not_names.iter() {
-        let _ = name_guess_experiments.read_negative_sample(&nn.text).unwrap();
+        name_guess_experiments.read_negative_sample(&nn.text).unwrap();
     }
     let mut random_names: Vec<String> = Vec::with_capacity(50);
     for _ in 0..50 {
@@ -140,3 +144,1652 @@ fn it_makes_a_random_generic_male_name() {
     random_names.iter().for_each(|n| print!("\"{n}\", "));
     print!("]");
 }
+
+#[test]
+fn it_counts_samples_read() {
+    let names: Vec<Name<16>> = Name::new_from_batch(
+        INPUT_ORC_NAMES,
+        "male",
+        name::PaddingBias::Left,
+        Some("Orc"), None, None, None
+    );
+    let not_names: Vec<Name<18>> = Name::new_from_batch(
+        NOT_NAMES,
+        "male",
+        name::PaddingBias::Left,
+        Some("Not"), None, None, None
+    );
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    for n in names.iter() {
+        name_guess_experiments.read_positive_sample(&n.text).unwrap();
+    }
+    for nn in not_names.iter() {
+        name_guess_experiments.read_negative_sample(&nn.text).unwrap();
+    }
+    assert_eq!(name_guess_experiments.total_positive_samples(), INPUT_ORC_NAMES.len());
+    assert_eq!(name_guess_experiments.total_negative_samples(), NOT_NAMES.len());
+    assert_eq!(name_guess_experiments.distinct_names_seen(), INPUT_ORC_NAMES.len() + NOT_NAMES.len());
+}
+
+#[test]
+fn it_reads_right_padded_names_like_left_padded_names() {
+    let left: Vec<Name<16>> = Name::new_from_batch(
+        INPUT_ORC_NAMES,
+        "male",
+        name::PaddingBias::Left,
+        Some("Orc"), None, None, None
+    );
+    let right: Vec<Name<16>> = Name::new_from_batch(
+        INPUT_ORC_NAMES,
+        "male",
+        name::PaddingBias::Right,
+        Some("Orc"), None, None, None
+    );
+    assert_eq!(right[0].text[16-8..], left[0].text[..8]);
+    let mut left_experiments: NameExperiments<3> = NameExperiments::new();
+    let mut right_experiments: NameExperiments<3> = NameExperiments::new();
+    for (l, r) in left.iter().zip(right.iter()) {
+        left_experiments.read_positive_sample(&l.text).unwrap();
+        right_experiments.read_positive_sample(&r.text).unwrap();
+    }
+    assert_eq!(left_experiments.positive_char_samples.weights, right_experiments.positive_char_samples.weights);
+    assert_eq!(left_experiments.positive_char_type_samples.weights, right_experiments.positive_char_type_samples.weights);
+    assert_eq!(left_experiments.name_sizes, right_experiments.name_sizes);
+    let new_name = right_experiments.build_random_name(Some(16)).unwrap();
+    println!("Hello, {}!", new_name);
+}
+
+#[test]
+fn it_reports_diversity_of_generated_names() {
+    let names: Vec<String> = ["grok", "mog", "grok", "urg", "mog", "grok"].iter().map(|s| s.to_string()).collect();
+    let report = DiversityReport::from_names(&names);
+    assert_eq!(report.total_names, 6);
+    assert_eq!(report.unique_names, 3);
+    assert_eq!(report.type_token_ratio, 0.5);
+    assert_eq!(report.most_repeated, vec![("grok".to_string(), 3), ("mog".to_string(), 2)]);
+
+    let names: Vec<Name<16>> = Name::new_from_batch(
+        INPUT_GOBLIN_NAMES,
+        "male",
+        name::PaddingBias::Left,
+        Some("Goblin"), None, None, None
+    );
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    for n in names.iter() {
+        name_guess_experiments.read_positive_sample(&n.text).unwrap();
+    }
+    let report = name_guess_experiments.sample_diversity(20).unwrap();
+    assert_eq!(report.total_names, 20);
+    assert!(report.unique_names >= 1 && report.unique_names <= 20);
+}
+
+#[test]
+fn it_trains_from_a_stream_and_skips_unusable_lines() {
+    let long_line = "a".repeat(NameExperiments::<3>::MAX_NAME_LENGTH + 1);
+    let corpus = format!("{}\n\n   \n{long_line}\nZug Zug\n", INPUT_ORC_NAMES.join("\n"));
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    let trained = name_guess_experiments.train_positive_stream(corpus.as_bytes()).unwrap();
+    assert_eq!(trained, INPUT_ORC_NAMES.len() + 1);
+    assert_eq!(name_guess_experiments.total_positive_samples(), trained);
+
+    let mut from_batch: NameExperiments<3> = NameExperiments::new();
+    let names: Vec<Name<16>> = Name::new_from_batch(
+        INPUT_ORC_NAMES,
+        "male",
+        name::PaddingBias::Left,
+        Some("Orc"), None, None, None
+    );
+    let mut from_stream: NameExperiments<3> = NameExperiments::new();
+    for n in names.iter() {
+        from_batch.read_positive_sample(&n.text).unwrap();
+    }
+    from_stream.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    assert_eq!(from_batch.positive_char_samples.weights, from_stream.positive_char_samples.weights);
+}
+
+#[test]
+fn it_exports_observed_transitions_as_dot() {
+    let mut name_guess_experiments: NameExperiments<2> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("ab".as_bytes()).unwrap();
+    let dot = name_guess_experiments.to_dot(0.05);
+    assert!(dot.starts_with("digraph NameExperiments {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert!(dot.contains("\"__\" -> \"_a\" [label=\"a "));
+    assert!(dot.contains("\"_a\" -> \"ab\" [label=\"b "));
+    assert!(dot.contains("\"ab\" -> \"<end>\" [label=\"_ "));
+    assert_eq!(dot.lines().count(), 5);
+    assert_eq!(name_guess_experiments.to_dot(0.0).lines().count(), 2 + 3 * 31);
+    // Every probability is ruled out, so no context has edges to normalize
+    name_guess_experiments.train_negative_stream("ab".as_bytes()).unwrap();
+    name_guess_experiments.set_generation_config(GenerationConfig { negative_influence: 1e6, ..Default::default() });
+    let dot = name_guess_experiments.to_dot(0.0);
+    assert!(!dot.contains("NaN"));
+    assert_eq!(dot.lines().count(), 2);
+}
+
+// Covers the broad classification, which the `articulation` feature subdivides
+#[cfg(not(feature = "articulation"))]
+#[test]
+fn it_builds_classification_windows_of_any_length() {
+    let preceding = [ValidChar::n, ValidChar::i, ValidChar::c, ValidChar::h];
+    assert_eq!(CharType::window(&preceding, ValidChar::e), [ValidChar::i, ValidChar::c, ValidChar::h, ValidChar::e]);
+    assert_eq!(CharType::window(&[ValidChar::s], ValidChar::c), [ValidChar::null, ValidChar::null, ValidChar::s, ValidChar::c]);
+    assert_eq!(CharType::classify(&[ValidChar::s, ValidChar::c]), Ok(CharType::Silent));
+    assert_eq!(CharType::classify(&[ValidChar::c]), Ok(CharType::Plosive));
+    assert!(CharType::classify(&[]).is_err());
+}
+
+#[test]
+fn it_makes_a_random_name_with_two_character_ngrams() {
+    let names: Vec<Name<16>> = Name::new_from_batch(
+        INPUT_ORC_NAMES,
+        "male",
+        name::PaddingBias::Left,
+        Some("Orc"), None, None, None
+    );
+    let mut name_guess_experiments: NameExperiments<2> = NameExperiments::new();
+    for n in names.iter() {
+        name_guess_experiments.read_positive_sample(&n.text).unwrap();
+    }
+    let new_name = name_guess_experiments.build_random_name(Some(16)).unwrap();
+    println!("Hello, {}!", new_name);
+}
+
+#[test]
+fn it_measures_divergence_between_corpora() {
+    let mut orcs: NameExperiments<3> = NameExperiments::new();
+    let mut more_orcs: NameExperiments<3> = NameExperiments::new();
+    let mut goblins: NameExperiments<3> = NameExperiments::new();
+    orcs.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    more_orcs.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    goblins.train_positive_stream(INPUT_GOBLIN_NAMES.join("\n").as_bytes()).unwrap();
+    assert_eq!(orcs.divergence_from(&more_orcs), 0.0);
+    let orc_goblin = orcs.divergence_from(&goblins);
+    assert!(orc_goblin > 0.0 && orc_goblin <= 1.0);
+    assert!((orc_goblin - goblins.divergence_from(&orcs)).abs() < 1e-12);
+    assert_eq!(NameExperiments::<3>::new().divergence_from(&NameExperiments::new()), 0.0);
+}
+
+#[test]
+fn it_retries_name_generation() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_GOBLIN_NAMES.join("\n").as_bytes()).unwrap();
+    let (new_name, warning) = name_guess_experiments.build_random_name_resilient(3, Some(16)).unwrap();
+    assert!(warning.is_none());
+    assert!(new_name.len() <= 16);
+    assert!(name_guess_experiments.build_random_name_resilient(0, Some(16)).is_err());
+}
+
+// Covers the broad classification, which the `articulation` feature subdivides
+#[cfg(not(feature = "articulation"))]
+#[test]
+fn it_classifies_characters_without_context() {
+    assert_eq!(CharType::default_for(ValidChar::h), CharType::Fricative);
+    assert_eq!(CharType::default_for(ValidChar::a), CharType::VowelRoot);
+    assert_eq!(CharType::default_for(ValidChar::y), CharType::VowelRoot);
+    assert_eq!(CharType::default_for(ValidChar::null), CharType::Null);
+    for &ch in ValidChar::ALLCHARS.iter() {
+        assert_eq!(CharType::default_for(ch), CharType::try_from(&[ValidChar::null, ValidChar::null, ValidChar::null, ch]).unwrap());
+    }
+}
+
+#[test]
+fn it_learns_syllable_boundaries() {
+    let corpus = "gruk|thar\nmor|gash\nthro|gar\nuz|gor\nbrak|nul\ndrok|mar";
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(corpus.as_bytes()).unwrap();
+    let context = [ValidChar::r, ValidChar::u, ValidChar::k];
+    let (row, _) = name_guess_experiments.positive_char_samples.get_row_and_sum(&context).unwrap();
+    assert_eq!(row[ValidChar::syllable_break as usize], 1);
+    // "gruk|thar" is 8 letters long
+    assert_eq!(name_guess_experiments.name_sizes.0[8], 1);
+    assert_eq!(name_guess_experiments.name_sizes.0.len(), 9);
+    for _ in 0..20 {
+        let new_name = name_guess_experiments.build_random_name(Some(16)).unwrap();
+        assert!(!new_name.contains(ValidChar::SYLLABLE_BREAK));
+    }
+}
+
+#[test]
+fn it_generates_names_from_an_inference_view() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let model = name_guess_experiments.as_inference();
+    let copied = model;
+    let new_name = copied.build_random_name(Some(16)).unwrap();
+    println!("Hello, {}!", new_name);
+    let (_, direct_sum, _) = name_guess_experiments.generate_probability_distribution(&[ValidChar::null; 3], &[CharType::Null; 3], 0, None, None, None, None).unwrap();
+    let (_, view_sum, _) = model.generate_probability_distribution(&[ValidChar::null; 3], &[CharType::Null; 3], 0, None, None, None, None).unwrap();
+    assert_eq!(direct_sum, view_sum);
+}
+
+#[test]
+fn it_casefolds_characters_without_panicking() {
+    assert_eq!(ValidChar::try_from(&'A'), Ok(ValidChar::a));
+    assert_eq!(ValidChar::try_from(&'z'), Ok(ValidChar::z));
+    assert_eq!(ValidChar::try_from(&'|'), Ok(ValidChar::syllable_break));
+    // Lowercases to 'i' followed by a combining dot
+    assert!(ValidChar::try_from(&'\u{130}').is_err());
+    // Capital sharp s lowercases to 'ß'
+    assert!(ValidChar::try_from(&'\u{1E9E}').is_err());
+    assert!(ValidChar::try_from(&'\u{3A3}').is_err());
+    assert_eq!(ValidChar::try_from(&' '), Ok(ValidChar::space));
+    assert!(ValidChar::try_from(&'_').is_err());
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("\u{130}stanbul\n\u{1E9E}trasse".as_bytes()).unwrap();
+}
+
+#[test]
+fn it_samples_the_same_model_with_different_strategies() {
+    let probabilities = [0.1, 0.5, 0.0, 0.4];
+    assert_eq!(SamplingStrategy::Greedy.pick(&probabilities, 1.0, &mut fastrand::Rng::new()), Ok(1));
+    for _ in 0..20 {
+        let pick = SamplingStrategy::Uniform(2).pick(&probabilities, 1.0, &mut fastrand::Rng::new()).unwrap();
+        assert!(pick == 1 || pick == 3);
+        assert_ne!(SamplingStrategy::Uniform(4).pick(&probabilities, 1.0, &mut fastrand::Rng::new()).unwrap(), 2);
+    }
+    assert!(SamplingStrategy::Uniform(0).pick(&probabilities, 1.0, &mut fastrand::Rng::new()).is_err());
+    assert_eq!(SamplingStrategy::default(), SamplingStrategy::Proportional);
+
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let greedy_name = name_guess_experiments.build_random_name_with_strategy(Some(16), SamplingStrategy::Greedy).unwrap();
+    assert_eq!(greedy_name, name_guess_experiments.build_random_name_with_strategy(Some(16), SamplingStrategy::Greedy).unwrap());
+    let new_name = name_guess_experiments.build_random_name_with_strategy(Some(16), SamplingStrategy::Uniform(3)).unwrap();
+    assert!(new_name.len() <= 16);
+}
+
+#[test]
+fn it_tracks_name_lengths_per_label() {
+    let orcs: Vec<Name<16>> = Name::new_from_batch(&["grukthar", "morgash"], "male", name::PaddingBias::Left, Some("Orc"), None, None, None);
+    let goblins: Vec<Name<16>> = Name::new_from_batch(&["zik", "nib", "gob"], "male", name::PaddingBias::Left, Some("Goblin"), None, None, None);
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    for n in orcs.iter() {
+        name_guess_experiments.read_labeled_positive_sample(&n.text, "orc").unwrap();
+    }
+    for n in goblins.iter() {
+        name_guess_experiments.read_labeled_positive_sample(&n.text, "goblin").unwrap();
+    }
+    name_guess_experiments.read_positive_sample(&goblins[0].text).unwrap();
+    assert_eq!(name_guess_experiments.name_sizes.1, 6);
+    assert_eq!(name_guess_experiments.labeled_name_sizes["goblin"], (vec![0, 0, 0, 3], 3));
+    assert_eq!(name_guess_experiments.labeled_name_sizes["orc"].1, 2);
+    assert_eq!(name_guess_experiments.labels().collect::<Vec<&str>>(), vec!["goblin", "orc"]);
+    for _ in 0..20 {
+        let new_name = name_guess_experiments.build_random_name_for("goblin", Some(16)).unwrap();
+        assert!(new_name.len() < "morgash".len());
+    }
+    assert!(name_guess_experiments.build_random_name_for("elf", Some(16)).is_err());
+}
+
+#[test]
+fn it_makes_a_name_like_an_exemplar() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    for _ in 0..20 {
+        let new_name = name_guess_experiments.build_similar_name("Grukthar", Some(16)).unwrap();
+        assert!(new_name.starts_with("gr"));
+        assert!(new_name.len() <= 16);
+    }
+    assert!(name_guess_experiments.build_similar_name("gr|ukthar", Some(16)).unwrap().starts_with("gr"));
+    assert_eq!(name_guess_experiments.build_similar_name("Grukthar", Some(1)).unwrap().len(), 1);
+    assert!(name_guess_experiments.build_similar_name("Gruk_thar", Some(16)).is_err());
+    assert!(name_guess_experiments.build_similar_name("Gr7k", Some(16)).is_err());
+    assert!(name_guess_experiments.build_similar_name("Gr\u{fc}k", Some(16)).unwrap().starts_with("gr"));
+    assert!(name_guess_experiments.build_similar_name("", Some(16)).is_err());
+}
+
+#[test]
+fn it_estimates_confidence_in_transitions() {
+    let mut name_guess_experiments: NameExperiments<2> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("ab\nab\nab\nac".as_bytes()).unwrap();
+    let estimates = name_guess_experiments.transition_confidence(&[ValidChar::null, ValidChar::a], 1.96).unwrap();
+    let b = estimates[ValidChar::b as usize];
+    let c = estimates[ValidChar::c as usize];
+    assert_eq!(b.observations, 3);
+    assert_eq!(b.probability, 0.75);
+    assert!(b.lower < 0.75 && b.upper > 0.75 && b.upper <= 1.0);
+    assert_eq!(c.probability, 0.25);
+    assert!(c.lower >= 0.0 && c.lower < c.probability);
+    assert_eq!(estimates[ValidChar::z as usize].lower, 0.0);
+    let unseen = name_guess_experiments.transition_confidence(&[ValidChar::z, ValidChar::z], 1.96).unwrap();
+    assert_eq!((unseen[0].probability, unseen[0].lower, unseen[0].upper), (0.0, 0.0, 1.0));
+    assert!(name_guess_experiments.transition_confidence(&[ValidChar::a], 1.96).is_err());
+}
+
+#[test]
+fn it_excludes_null_from_a_probability_distribution() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let char_seq = [ValidChar::g, ValidChar::a, ValidChar::r];
+    let char_type_seq = char_seq.map(CharType::default_for);
+    let (with_null, with_null_sum, _) = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 8, None, None, None, None).unwrap();
+    let (without_null, without_null_sum, _) = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 8, None, None, None, Some(true)).unwrap();
+    let null_index = ValidChar::null as usize;
+    assert!(with_null[null_index] > 0.0);
+    assert_eq!(without_null[null_index], 0.0);
+    assert_eq!(with_null[..null_index], without_null[..null_index]);
+    assert!((with_null_sum - with_null[null_index] - without_null_sum).abs() < 1e-12);
+}
+
+#[test]
+fn it_builds_a_model_from_labeled_corpora() {
+    let mut builder: ModelBuilder<3> = ModelBuilder::new();
+    builder.add_corpus("Orc", INPUT_ORC_NAMES).unwrap()
+        .add_corpus(" Goblin ", INPUT_GOBLIN_NAMES).unwrap()
+        .add_corpus("Orc", &["Zug"]).unwrap();
+    assert!(builder.add_corpus("", INPUT_ORC_NAMES).is_err());
+    assert!(builder.add_corpus("a label that is far too long", INPUT_ORC_NAMES).is_err());
+    assert!(builder.add_corpus("Troll", &["Grok", "  "]).is_err());
+    assert_eq!(builder.sample_counts(), vec![("Orc", INPUT_ORC_NAMES.len() + 1), ("Goblin", INPUT_GOBLIN_NAMES.len())]);
+    let name_guess_experiments = builder.build();
+    assert_eq!(name_guess_experiments.total_positive_samples(), INPUT_ORC_NAMES.len() + 1 + INPUT_GOBLIN_NAMES.len());
+    assert!(name_guess_experiments.build_random_name_for("Troll", Some(16)).is_err());
+    let new_name = name_guess_experiments.build_random_name_for("Goblin", Some(16)).unwrap();
+    println!("Hello, {}!", new_name);
+}
+
+#[test]
+fn it_builds_a_model_with_the_weights_of_each_corpus() {
+    let mut builder: ModelBuilder<3> = ModelBuilder::new();
+    builder.add_corpus("female", &["Elina", "Alani", "Nalia", "Lenia", "Ilena", "Anela"]).unwrap()
+        .add_corpus("male", &["Grukor", "Orgruk", "Korgur", "Rugrok", "Gorruk", "Urkog"]).unwrap();
+    let mut name_guess_experiments = builder.build();
+    name_guess_experiments.set_generation_config(GenerationConfig { epsilon: 0.05, ..Default::default() });
+    let share_of_letters = |label: &str, letters: &str| -> f64 {
+        let names = (0..200).map(|_| name_guess_experiments.build_random_name_for(label, Some(16)).unwrap()).collect::<String>();
+        names.chars().filter(|&c| letters.contains(c)).count() as f64 / names.chars().count() as f64
+    };
+    assert!(share_of_letters("female", "aeiln") > 2.0 * share_of_letters("male", "aeiln"));
+    assert!(share_of_letters("male", "gkoru") > 2.0 * share_of_letters("female", "gkoru"));
+}
+
+#[test]
+fn it_warns_about_degenerate_models() {
+    let empty: NameExperiments<3> = NameExperiments::new();
+    assert_eq!(empty.health_check(), vec![Warning::NoPositiveSamples, Warning::FewContexts { observed: 0 }]);
+
+    let mut tiny: NameExperiments<3> = NameExperiments::new();
+    tiny.train_positive_stream("grok\nmog\nurg".as_bytes()).unwrap();
+    tiny.train_negative_stream("xqzt\nxqzt\nxqzt\nxqzt".as_bytes()).unwrap();
+    let warnings = tiny.health_check();
+    assert!(matches!(warnings[0], Warning::FewContexts { observed: 11 }));
+    assert!(matches!(warnings[1], Warning::DominantLength { length: 4, .. }));
+    assert!(matches!(warnings[2], Warning::NegativeOverwhelms { .. }));
+    assert_eq!(warnings[0].to_string(), "fewer than 30 distinct contexts observed (11)");
+
+    let mut healthy: NameExperiments<3> = NameExperiments::new();
+    healthy.train_positive_stream(INPUT_EUROPEAN_MALE_NAMES.join("\n").as_bytes()).unwrap();
+    assert_eq!(healthy.health_check(), vec![]);
+}
+
+#[test]
+fn it_makes_compound_names() {
+    assert_eq!(char::from(ValidChar::space), ' ');
+    assert_eq!(ValidChar::try_from(ValidChar::space as u8), Ok(ValidChar::space));
+    assert_eq!(CharType::default_for(ValidChar::space), CharType::SemiPunctuation);
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("iron maul\nstone fist\nblack axe\nred hand".as_bytes()).unwrap();
+    let (row, _) = name_guess_experiments.positive_char_samples.get_row_and_sum(&[ValidChar::r, ValidChar::o, ValidChar::n]).unwrap();
+    assert_eq!(row[ValidChar::space as usize], 1);
+    assert_eq!(name_guess_experiments.name_sizes.0[9], 2);
+    let (first, _, _) = name_guess_experiments.generate_probability_distribution(&[ValidChar::null; 3], &[CharType::Null; 3], 0, None, None, None, None).unwrap();
+    assert_eq!(first[ValidChar::space as usize], 0.0);
+    for _ in 0..20 {
+        let new_name = name_guess_experiments.build_random_name(Some(16)).unwrap();
+        assert!(!new_name.starts_with(' ') && !new_name.ends_with(' ') && !new_name.contains("  "));
+    }
+}
+
+#[test]
+fn it_estimates_the_expected_generated_length() {
+    assert_eq!(NameExperiments::<3>::new().expected_generated_length(), 0.0);
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("grok\nmogg\nurgh\nzugg".as_bytes()).unwrap();
+    let expected_length = name_guess_experiments.expected_generated_length();
+    assert!((1.0..=16.0).contains(&expected_length));
+}
+
+#[test]
+fn it_reproduces_names_from_a_seed() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_GOBLIN_NAMES.join("\n").as_bytes()).unwrap();
+    name_guess_experiments.seed(42);
+    let first: Vec<String> = (0..10).map(|_| name_guess_experiments.build_random_name(Some(16)).unwrap()).collect();
+    name_guess_experiments.seed(42);
+    let second: Vec<String> = (0..10).map(|_| name_guess_experiments.as_inference().build_random_name(Some(16)).unwrap()).collect();
+    assert_eq!(first, second);
+    name_guess_experiments.clear_seed();
+    let new_name = name_guess_experiments.build_random_name(Some(16)).unwrap();
+    println!("Hello, {}!", new_name);
+}
+
+#[test]
+fn it_rejects_mismatched_context_lengths() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let err = name_guess_experiments.generate_probability_distribution(&[ValidChar::null; 2], &[CharType::Null; 3], 0, None, None, None, None).unwrap_err();
+    assert!(err.contains("N=3") && err.contains("char_seq has length 2") && err.contains("char_type_seq has length 3"));
+    assert!(name_guess_experiments.generate_probability_distribution(&[ValidChar::null; 4], &[CharType::Null; 3], 0, None, None, None, None).is_err());
+    assert!(name_guess_experiments.guess_next_char(&[ValidChar::null; 3], &[CharType::Null; 2], 0).is_err());
+    assert!(name_guess_experiments.generate_probability_distribution(&[ValidChar::null; 4], &[CharType::Null; 4], 0, None, None, None, None).is_ok());
+}
+
+#[test]
+fn it_avoids_blocked_names() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("grok\ngrak\nmog\nmug".as_bytes()).unwrap();
+    let blocklist: HashSet<String> = ["MOG".to_string()].into_iter().collect();
+    for _ in 0..20 {
+        let new_name = name_guess_experiments.build_random_name_filtered(&blocklist, &["Gr"], Some(16), 200).unwrap();
+        assert!(new_name != "mog" && !new_name.contains("gr"));
+    }
+    assert!(name_guess_experiments.build_random_name_filtered(&blocklist, &[""], Some(16), 50).is_err());
+    assert!(name_guess_experiments.build_random_name_filtered(&HashSet::new(), &[], Some(16), 0).is_err());
+}
+
+#[test]
+fn it_sharpens_tiny_probabilities_without_underflow() {
+    let mut name_guess_experiments: NameExperiments<2> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("ab".as_bytes()).unwrap();
+    // The character context expects 'a' while the character type context expects a plosive. Every entry is a product of tiny easing terms
+    let (probabilities, sum_of_probabilities, _) = name_guess_experiments.generate_probability_distribution(
+        &[ValidChar::null; 2], &[CharType::Null, CharType::VowelRoot], 1, Some(1e-170), None, Some(true), None
+    ).unwrap();
+    assert!(sum_of_probabilities > 0.0 && sum_of_probabilities.is_finite());
+    assert!(SamplingStrategy::Proportional.pick(&probabilities, sum_of_probabilities, &mut fastrand::Rng::new()).is_ok());
+}
+
+#[test]
+fn it_treats_the_terminator_as_the_end_of_a_name() {
+    assert!(ValidChar::is_terminator(Name::<16>::TERMINATOR));
+    assert!(ValidChar::is_terminator(char::from(ValidChar::null)));
+    assert!(!ValidChar::is_terminator('a'));
+    let name: Name<8> = Name::new("gru_k", "male", name::PaddingBias::Left, None, None, None, None);
+    assert_eq!(name.text[..4], [Some('g'), Some('r'), Some('u'), None]);
+    let mut terminated: NameExperiments<3> = NameExperiments::new();
+    let mut plain: NameExperiments<3> = NameExperiments::new();
+    terminated.read_positive_sample(&[Some('_'), Some('g'), Some('r'), Some('u'), Some('_'), Some('k')]).unwrap();
+    plain.read_positive_sample(&[None, Some('g'), Some('r'), Some('u'), None]).unwrap();
+    assert_eq!(terminated.positive_char_samples.weights, plain.positive_char_samples.weights);
+    assert_eq!(terminated.name_sizes, plain.name_sizes);
+}
+
+#[test]
+fn it_learns_capitalization_templates() {
+    let names: Vec<Name<16>> = Name::new_from_batch(&["McTavish", "O'Brien", "McLeod", "grok"], "male", name::PaddingBias::Left, None, None, None, None);
+    assert_eq!(names[0].text[2], Some('T'));
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    for n in names.iter() {
+        name_guess_experiments.read_positive_sample(&n.text).unwrap();
+    }
+    name_guess_experiments.train_positive_stream("Gruk|Thar".as_bytes()).unwrap();
+    name_guess_experiments.train_negative_stream("XQZT".as_bytes()).unwrap();
+    assert_eq!(name_guess_experiments.capitalization_templates(), &[(vec![0, 2], 3), (vec![], 1), (vec![0, 4], 1)]);
+    assert_eq!(NameExperiments::<3>::new().apply_capitalization_template("mcleod"), "mcleod");
+
+    let mut cased: NameExperiments<3> = NameExperiments::new();
+    cased.train_positive_stream("MacDuff".as_bytes()).unwrap();
+    assert_eq!(cased.apply_capitalization_template("macbeth"), "MacBeth");
+    assert_eq!(cased.apply_capitalization_template("ma"), "Ma");
+    assert_eq!(cased.build_random_name_cased(Some(16)).unwrap().chars().next().map(|c| c.is_uppercase()), Some(true));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn it_samples_names_as_a_rand_distribution() {
+    use rand::{Rng, SeedableRng};
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let first: Vec<String> = rand::rngs::StdRng::seed_from_u64(7).sample_iter(&name_guess_experiments).take(10).collect();
+    let second: Vec<String> = rand::rngs::StdRng::seed_from_u64(7).sample_iter(name_guess_experiments.as_inference()).take(10).collect();
+    assert_eq!(first, second);
+    assert!(first.iter().all(|n| !n.is_empty() && n.len() <= 16));
+    // Generation fails part way through for an untrained experiment, which must not panic
+    let untrained: NameExperiments<3> = NameExperiments::new();
+    let _ = rand::thread_rng().sample(&untrained);
+}
+
+#[test]
+fn it_compares_positive_and_negative_observations_of_a_context() {
+    let mut name_guess_experiments: NameExperiments<2> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("ab\nac".as_bytes()).unwrap();
+    name_guess_experiments.train_negative_stream("ax\nay\naz".as_bytes()).unwrap();
+    assert_eq!(name_guess_experiments.context_balance(&[ValidChar::null, ValidChar::a]), Ok((2, 3)));
+    assert_eq!(name_guess_experiments.context_balance(&[ValidChar::q, ValidChar::q]), Ok((0, 0)));
+    assert!(name_guess_experiments.context_balance(&[ValidChar::a]).is_err());
+}
+
+#[test]
+fn it_backtracks_out_of_dead_ends() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_GOBLIN_NAMES.join("\n").as_bytes()).unwrap();
+    assert_eq!(name_guess_experiments.generation_config(), &GenerationConfig::default());
+    name_guess_experiments.seed(11);
+    let plain: Vec<String> = (0..10).map(|_| name_guess_experiments.build_random_name(Some(16)).unwrap()).collect();
+    // Without any backtracks to spend a dead end changes nothing
+    name_guess_experiments.set_generation_config(GenerationConfig { backtrack_threshold: 2.0, max_backtracks: 0, ..Default::default() });
+    name_guess_experiments.seed(11);
+    let unchanged: Vec<String> = (0..10).map(|_| name_guess_experiments.build_random_name(Some(16)).unwrap()).collect();
+    assert_eq!(plain, unchanged);
+    // Every context is a dead end, so every backtrack is spent before the name can finish
+    name_guess_experiments.set_generation_config(GenerationConfig { backtrack_threshold: 2.0, max_backtracks: 25, ..Default::default() });
+    for _ in 0..10 {
+        let new_name = name_guess_experiments.build_random_name(Some(16)).unwrap();
+        assert!(!new_name.is_empty() && new_name.len() <= 16);
+    }
+    name_guess_experiments.set_generation_config(GenerationConfig { backtrack_threshold: 0.05, max_backtracks: 2, ..Default::default() });
+    let new_name = name_guess_experiments.build_random_name(Some(16)).unwrap();
+    println!("Hello, {}!", new_name);
+}
+
+#[test]
+fn it_collapses_near_duplicate_names() {
+    let names = ["Grukthar", "Grukthor", "grukthar", "  ", "Morgash", "Morgosh", "Kitten", "Sitting"];
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    assert_eq!(name_guess_experiments.train_positive_deduped_fuzzy(&names, 1).unwrap(), 3);
+    assert_eq!(name_guess_experiments.total_positive_samples(), 4);
+    let mut exact: NameExperiments<3> = NameExperiments::new();
+    assert_eq!(exact.train_positive_deduped_fuzzy(&names, 0).unwrap(), 1);
+    let mut loose: NameExperiments<3> = NameExperiments::new();
+    // "Kitten" and "Sitting" are three edits apart
+    assert_eq!(loose.train_positive_deduped_fuzzy(&names, 3).unwrap(), 4);
+}
+
+#[test]
+fn it_finds_the_most_probable_names() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let top = name_guess_experiments.top_names(5, 16).unwrap();
+    assert_eq!(top.len(), 5);
+    assert!(top.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    assert!(top.iter().all(|(name, p)| !name.is_empty() && name.len() <= 16 && *p > 0.0 && *p <= 1.0));
+    assert_eq!(top, name_guess_experiments.top_names(5, 16).unwrap());
+    assert!(name_guess_experiments.top_names(0, 16).unwrap().is_empty());
+    assert!(name_guess_experiments.top_names(5, 2).unwrap().iter().all(|(name, _)| name.len() <= 2));
+}
+
+#[test]
+fn it_trains_from_labeled_pairs() {
+    let samples = INPUT_ORC_NAMES.iter().map(|&n| (n, true))
+        .chain(NOT_NAMES.iter().map(|&n| (n, false)))
+        .chain([("   ", true), ("", false)]);
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    let (positives, negatives) = name_guess_experiments.train_labeled(samples).unwrap();
+    assert_eq!((positives, negatives), (INPUT_ORC_NAMES.len(), NOT_NAMES.len()));
+    assert_eq!(name_guess_experiments.total_positive_samples(), INPUT_ORC_NAMES.len());
+    assert_eq!(name_guess_experiments.total_negative_samples(), NOT_NAMES.len());
+}
+
+#[test]
+fn it_aggregates_weights_into_bigram_counts() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("ab\nab\nac".as_bytes()).unwrap();
+    let bigrams = name_guess_experiments.bigram_counts();
+    let (a, b, c, null) = (ValidChar::a as usize, ValidChar::b as usize, ValidChar::c as usize, ValidChar::null as usize);
+    assert_eq!(bigrams[null][a], 3);
+    assert_eq!(bigrams[a][b], 2);
+    assert_eq!(bigrams[a][c], 1);
+    assert_eq!(bigrams[b][null] + bigrams[c][null], 3);
+    assert_eq!(bigrams.iter().flatten().sum::<u32>(), 9);
+    // Aggregates are not capped by the u8 weights
+    let mut saturated: NameExperiments<2> = NameExperiments::new();
+    let _ = saturated.train_positive_stream("ab\nbb\n".repeat(200).as_bytes());
+    assert!(saturated.bigram_counts()[b].iter().sum::<u32>() > u8::MAX as u32);
+}
+
+#[test]
+fn it_trims_trailing_separators() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("grok-\nmog'\nurg-\n-zug\n'nar".as_bytes()).unwrap();
+    let untrimmed: Vec<String> = (0..200).map(|_| name_guess_experiments.build_random_name(Some(16)).unwrap()).collect();
+    assert!(untrimmed.iter().all(|n| !n.starts_with(['-', '\''])));
+    assert!(untrimmed.iter().any(|n| n.ends_with(['-', '\''])));
+    name_guess_experiments.set_generation_config(GenerationConfig { trim_trailing_separators: true, ..Default::default() });
+    for _ in 0..200 {
+        let new_name = name_guess_experiments.build_random_name(Some(16)).unwrap();
+        assert!(!new_name.starts_with(['-', '\'']) && !new_name.ends_with(['-', '\'', ' ']), "{new_name}");
+    }
+}
+
+#[test]
+fn it_scales_the_influence_of_negative_samples() {
+    let mut positive_only: NameExperiments<3> = NameExperiments::new();
+    positive_only.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let mut with_negatives: NameExperiments<3> = NameExperiments::new();
+    with_negatives.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    with_negatives.train_negative_stream(NOT_NAMES.join("\n").as_bytes()).unwrap();
+    let char_seq = [ValidChar::null, ValidChar::null, ValidChar::null];
+    let char_type_seq = [CharType::Null, CharType::Null, CharType::Null];
+    let normalized = |experiments: &NameExperiments<3>| -> Vec<f64> {
+        let (probabilities, sum, _) = experiments.generate_probability_distribution(&char_seq, &char_type_seq, 0, None, None, Some(false), None).unwrap();
+        probabilities.iter().map(|p| p / sum).collect()
+    };
+    let full = normalized(&with_negatives);
+    with_negatives.set_generation_config(GenerationConfig { negative_influence: 0.0, ..Default::default() });
+    let ignored = normalized(&with_negatives);
+    assert!(ignored.iter().zip(normalized(&positive_only).iter()).all(|(a, b)| (a - b).abs() < 1e-12));
+    assert!(full.iter().zip(ignored.iter()).any(|(a, b)| (a - b).abs() > 1e-6));
+    with_negatives.set_generation_config(GenerationConfig { negative_influence: 1.0, ..Default::default() });
+    assert_eq!(normalized(&with_negatives), full);
+}
+
+#[test]
+fn it_streams_names_without_repeats_until_exhausted() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let names: Vec<String> = name_guess_experiments.unique_name_stream(Some(16)).take(50).map(|n| n.unwrap()).collect();
+    assert_eq!(names.iter().collect::<HashSet<_>>().len(), 50);
+    // Only single character names can be produced, so the reachable names run out
+    let mut stream = name_guess_experiments.unique_name_stream(Some(1));
+    let short_names: Vec<String> = stream.by_ref().map(|n| n.unwrap()).collect();
+    assert!(stream.is_exhausted());
+    assert!(short_names.len() <= ValidChar::VARIANTCOUNT as usize && short_names.len() == stream.yielded_count());
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn it_terminates_a_name_only_once() {
+    let read = |text: &str| -> NameExperiments<3> {
+        let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+        let text: Vec<Option<char>> = text.chars().map(Some).collect();
+        name_guess_experiments.read_positive_sample(&text).unwrap();
+        name_guess_experiments
+    };
+    let plain = read("grok");
+    for terminated in ["grok_", "grok\0", "grok7"] {
+        let terminated = read(terminated);
+        assert_eq!(terminated.positive_char_samples.weights, plain.positive_char_samples.weights);
+        assert_eq!(terminated.positive_char_samples.sum, plain.positive_char_samples.sum);
+    }
+}
+
+/// Only holds rows for the sequences that have been observed
+struct SparseWeights<const N: usize, const V: usize> {
+    rows: HashMap<Vec<usize>, ([u8; V], usize)>,
+}
+
+impl<const N: usize, const V: usize> SparseWeights<N, V> {
+    fn key<T>(char_seq: &[T]) -> Result<Vec<usize>, String> where usize: From<T>, T: Copy {
+        if char_seq.len() < N {return Err("Not enough characters given to determine row".to_string())}
+        Ok(char_seq.iter().take(N).map(|&c| usize::from(c)).collect())
+    }
+}
+
+impl<const N: usize, const V: usize> WeightStore<V> for SparseWeights<N, V> {
+    type Counter = u8;
+
+    fn new() -> Self {
+        SparseWeights { rows: HashMap::new() }
+    }
+    fn get_row<T>(&self, char_seq: &[T]) -> Result<[u8; V], String> where usize: From<T>, T: Clone + Copy + Debug {
+        Ok(self.rows.get(&Self::key(char_seq)?).map_or([0; V], |(row, _)| *row))
+    }
+    fn get_mut_row<T>(&mut self, char_seq: &[T]) -> Result<&mut [u8; V], String> where usize: From<T>, T: Clone + Copy + Debug {
+        Ok(&mut self.rows.entry(Self::key(char_seq)?).or_insert(([0; V], 0)).0)
+    }
+    fn add<T>(&mut self, sequence: &[T], following_char: &T) -> Result<(), String> where usize: From<T>, T: Clone + Copy + Debug {
+        let (row, sum) = self.rows.entry(Self::key(sequence)?).or_insert(([0; V], 0));
+        let column = usize::from(*following_char);
+        row[column] = row[column].checked_add(1).ok_or("Weights max capacity reached")?;
+        *sum += 1;
+        Ok(())
+    }
+    fn sum<T>(&self, char_seq: &[T]) -> Result<usize, String> where usize: From<T>, T: Clone + Copy + Debug {
+        Ok(self.rows.get(&Self::key(char_seq)?).map_or(0, |&(_, sum)| sum))
+    }
+}
+
+#[test]
+fn it_generates_from_a_custom_weight_store() {
+    let mut dense: NameExperiments<3> = NameExperiments::new();
+    let mut sparse: NameExperiments<3, SparseWeights<3, { ValidChar::VARIANTCOUNT as usize }>> = NameExperiments::new();
+    dense.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    sparse.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    sparse.train_negative_stream(NOT_NAMES.join("\n").as_bytes()).unwrap();
+    dense.train_negative_stream(NOT_NAMES.join("\n").as_bytes()).unwrap();
+    let char_seq = [ValidChar::null, ValidChar::m, ValidChar::o];
+    let char_type_seq = [CharType::Null, CharType::Null, CharType::Null];
+    assert_eq!(
+        sparse.generate_probability_distribution(&char_seq, &char_type_seq, 2, None, None, None, None).unwrap(),
+        dense.generate_probability_distribution(&char_seq, &char_type_seq, 2, None, None, None, None).unwrap()
+    );
+    assert!(sparse.positive_char_samples.rows.len() < (ValidChar::VARIANTCOUNT as usize).pow(3));
+    sparse.seed(5);
+    dense.seed(5);
+    assert_eq!(sparse.build_random_name(Some(16)).unwrap(), dense.build_random_name(Some(16)).unwrap());
+}
+
+#[test]
+fn it_exports_the_length_histogram_as_csv() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    assert_eq!(name_guess_experiments.length_histogram_csv(), "length,count,probability\n0,0,0\n");
+    name_guess_experiments.train_positive_stream("grok\nmog\nurgh\nzu|gg".as_bytes()).unwrap();
+    assert_eq!(name_guess_experiments.length_histogram_csv(), "length,count,probability\n0,0,0\n1,0,0\n2,0,0\n3,1,0.25\n4,3,0.75\n");
+}
+
+#[test]
+fn it_eases_each_character_separately() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let char_seq = [ValidChar::null, ValidChar::m, ValidChar::o];
+    let char_type_seq = [CharType::Null, CharType::Null, CharType::Null];
+    let scalar = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 2, Some(0.5), None, Some(false), None).unwrap();
+    let broadcast = name_guess_experiments.generate_probability_distribution_with_easing(
+        &char_seq, &char_type_seq, 2, &[0.5; ValidChar::VARIANTCOUNT as usize], None, Some(false), None
+    ).unwrap();
+    assert_eq!(scalar, broadcast);
+    let mut pos_easing = [0.5; ValidChar::VARIANTCOUNT as usize];
+    pos_easing[ValidChar::q as usize] = 5.0;
+    let (eased, sum, _) = name_guess_experiments.generate_probability_distribution_with_easing(&char_seq, &char_type_seq, 2, &pos_easing, None, Some(false), None).unwrap();
+    let q = ValidChar::q as usize;
+    let z = ValidChar::z as usize;
+    assert!(eased[q] / sum > scalar.0[q] / scalar.1);
+    assert!(eased[z] / sum < scalar.0[z] / scalar.1);
+}
+
+#[test]
+fn it_bounds_the_attempts_of_a_unique_batch() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let (names, stopped_early) = name_guess_experiments.build_random_names_unique(20, 10_000, Some(16)).unwrap();
+    assert!(!stopped_early);
+    assert_eq!(names.iter().collect::<HashSet<_>>().len(), 20);
+    // Fewer single character names can be reached than are asked for
+    let (names, stopped_early) = name_guess_experiments.build_random_names_unique(100, 500, Some(1)).unwrap();
+    assert!(stopped_early && names.len() < 100);
+    let (names, stopped_early) = name_guess_experiments.build_random_names_unique(5, 0, Some(16)).unwrap();
+    assert!(stopped_early && names.is_empty());
+}
+
+// Covers the broad classification, which the `articulation` feature subdivides
+#[cfg(not(feature = "articulation"))]
+#[test]
+fn it_classifies_the_characters_of_a_name() {
+    let name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    assert_eq!(
+        name_guess_experiments.char_types_of("Chad_ignored").unwrap(),
+        vec![CharType::Plosive, CharType::Affricate, CharType::VowelRoot, CharType::FlipTap]
+    );
+    assert_eq!(name_guess_experiments.char_types_of("gn|a").unwrap()[1], CharType::Nasal);
+    assert!(name_guess_experiments.char_types_of("").unwrap().is_empty());
+    assert!(name_guess_experiments.char_types_of("gr7k").is_err());
+}
+
+#[test]
+fn it_restricts_names_to_typical_lengths() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("ug\ngrokk\nmoggu\nurgha\nzuggo\nbrakk\ngashu\nnargo\nsnakk\nmorgashnargul".as_bytes()).unwrap();
+    name_guess_experiments.set_generation_config(GenerationConfig { length_percentile_range: Some((0.2, 0.8)), ..Default::default() });
+    name_guess_experiments.seed(8);
+    let lengths: Vec<usize> = (0..50).map(|_| name_guess_experiments.build_random_name(Some(16)).unwrap().len()).collect();
+    // A trailing space is trimmed after generation, which rarely shortens a name
+    assert!(lengths.iter().all(|&length| length <= 5) && lengths.iter().filter(|&&length| length == 5).count() >= 45);
+    let mut single_length: NameExperiments<3> = NameExperiments::new();
+    single_length.train_positive_stream("grok\nmogg\nurgh".as_bytes()).unwrap();
+    single_length.set_generation_config(GenerationConfig { length_percentile_range: Some((0.1, 0.9)), ..Default::default() });
+    single_length.seed(8);
+    let lengths: Vec<usize> = (0..20).map(|_| single_length.build_random_name(Some(16)).unwrap().len()).collect();
+    assert!(lengths.iter().all(|&length| length <= 4) && lengths.iter().filter(|&&length| length == 4).count() >= 15);
+    single_length.set_generation_config(GenerationConfig { length_percentile_range: Some((0.9, 0.1)), ..Default::default() });
+    assert!(single_length.build_random_name(Some(16)).is_err());
+}
+
+#[test]
+fn it_estimates_the_unique_capacity_of_a_model() {
+    let mut single_letters: NameExperiments<3> = NameExperiments::new();
+    single_letters.train_positive_stream("a\nb".as_bytes()).unwrap();
+    single_letters.set_generation_config(GenerationConfig { length_percentile_range: Some((0.0, 1.0)), ..Default::default() });
+    // Only single character names can be produced, and each is produced many times
+    let capacity = single_letters.estimated_unique_capacity(2000);
+    assert!(capacity >= 2.0 && capacity <= ValidChar::VARIANTCOUNT as f64, "{capacity}");
+    let mut orcs: NameExperiments<3> = NameExperiments::new();
+    orcs.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    assert!(orcs.estimated_unique_capacity(300) > capacity);
+    assert_eq!(NameExperiments::<3>::new().estimated_unique_capacity(0), 0.0);
+}
+
+#[test]
+fn it_reads_character_types_in_the_same_pass_as_characters() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("Niche|tha\nschwa".as_bytes()).unwrap();
+    let mut expected: crate::NGramWeights<3, { CharType::VARIANTCOUNT }> = WeightStore::new();
+    for name in ["niche|tha", "schwa"] {
+        let mut context = [CharType::Null; 3];
+        for char_type in name_guess_experiments.char_types_of(name).unwrap() {
+            expected.add(&context, &char_type).unwrap();
+            context.rotate_left(1);
+            context[2] = char_type;
+        }
+    }
+    assert_eq!(name_guess_experiments.positive_char_type_samples.weights, expected.weights);
+    assert_eq!(name_guess_experiments.positive_char_type_samples.sum, expected.sum);
+    assert_eq!(name_guess_experiments.name_sizes.0[8], 1);
+}
+
+#[test]
+fn it_reinforces_generated_names_with_a_judge() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let mut judged = 0usize;
+    let accepted = name_guess_experiments.generate_and_reinforce(40, |name| {
+        judged += 1;
+        !name.contains('z')
+    }, Some(16)).unwrap();
+    assert!(accepted.iter().all(|name| !name.contains('z')));
+    assert_eq!(name_guess_experiments.total_positive_samples(), INPUT_ORC_NAMES.len() + accepted.len());
+    assert_eq!(name_guess_experiments.total_negative_samples(), judged - accepted.len());
+}
+
+#[test]
+fn it_compares_how_characters_behave() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("kar\ngar\nkor\ngor\nzin\nzir".as_bytes()).unwrap();
+    let interchangeable = name_guess_experiments.char_behavior_similarity(ValidChar::k, ValidChar::g);
+    assert!((interchangeable - 1.0).abs() < 1e-12);
+    assert!(name_guess_experiments.char_behavior_similarity(ValidChar::k, ValidChar::z).abs() < 1e-12);
+    let vowels = name_guess_experiments.char_behavior_similarity(ValidChar::a, ValidChar::i);
+    assert!(vowels > 0.0 && vowels < 1.0);
+    assert_eq!(name_guess_experiments.char_behavior_similarity(ValidChar::k, ValidChar::q), 0.0);
+}
+
+#[test]
+fn it_finalizes_negative_penalties() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    name_guess_experiments.train_negative_stream(NOT_NAMES.join("\n").as_bytes()).unwrap();
+    let char_seq = [ValidChar::null, ValidChar::null, ValidChar::null];
+    let char_type_seq = [CharType::Null, CharType::Null, CharType::Null];
+    let raw = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 0, None, None, Some(false), None).unwrap();
+    assert!(!name_guess_experiments.negatives_finalized());
+    name_guess_experiments.finalize_negatives();
+    assert!(name_guess_experiments.negatives_finalized());
+    let finalized = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 0, None, None, Some(false), None).unwrap();
+    assert_ne!(raw, finalized);
+    let penalties = name_guess_experiments.finalized_penalties(&char_seq).unwrap();
+    assert!(penalties.iter().all(|&p| (NameExperiments::<3>::MIN_NEGATIVE_PENALTY..=1.0).contains(&p)));
+    assert!(penalties.iter().any(|&p| p < 1.0));
+    // A context never observed in a negative sample is not penalized
+    assert_eq!(name_guess_experiments.finalized_penalties(&[ValidChar::q, ValidChar::q, ValidChar::q]), Some([1.0; ValidChar::VARIANTCOUNT as usize]));
+    assert!(name_guess_experiments.build_random_name(Some(16)).is_ok());
+    name_guess_experiments.train_negative_stream("xqz".as_bytes()).unwrap();
+    assert!(!name_guess_experiments.negatives_finalized());
+}
+
+#[test]
+fn it_makes_improbable_characters_impossible() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let char_seq = [ValidChar::null, ValidChar::null, ValidChar::null];
+    let char_type_seq = [CharType::Null, CharType::Null, CharType::Null];
+    let (eased, _, _) = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 0, None, None, None, None).unwrap();
+    assert!(eased[ValidChar::q as usize] > 0.0);
+    name_guess_experiments.set_generation_config(GenerationConfig { epsilon: 0.01, ..Default::default() });
+    let (floored, sum, _) = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 0, None, None, None, None).unwrap();
+    assert_eq!(floored[ValidChar::q as usize], 0.0);
+    assert!(floored.iter().all(|&p| p == 0.0 || p / sum >= 0.01));
+    // The most probable character survives any floor
+    name_guess_experiments.set_generation_config(GenerationConfig { epsilon: 1.0, ..Default::default() });
+    let (only, _, _) = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 0, None, None, None, None).unwrap();
+    assert_eq!(only.iter().filter(|&&p| p > 0.0).count(), 1);
+    assert!(name_guess_experiments.build_random_name(Some(16)).is_ok());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn it_round_trips_an_experiment_through_serde() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    name_guess_experiments.train_negative_stream(NOT_NAMES.join("\n").as_bytes()).unwrap();
+    let grukthar: Name<16> = Name::new("Grukthar", "male", name::PaddingBias::Left, None, None, None, None);
+    name_guess_experiments.read_positive_sample_for(&grukthar.text, "male").unwrap();
+    let json = serde_json::to_string(&name_guess_experiments).unwrap();
+    let mut restored: NameExperiments<3> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.positive_char_samples.weights, name_guess_experiments.positive_char_samples.weights);
+    assert_eq!(restored.negative_char_type_samples.sum, name_guess_experiments.negative_char_type_samples.sum);
+    assert_eq!(restored.name_sizes, name_guess_experiments.name_sizes);
+    name_guess_experiments.seed(21);
+    restored.seed(21);
+    for _ in 0..10 {
+        assert_eq!(restored.build_random_name(Some(16)).unwrap(), name_guess_experiments.build_random_name(Some(16)).unwrap());
+        assert_eq!(restored.build_random_name_for("male", Some(16)).unwrap(), name_guess_experiments.build_random_name_for("male", Some(16)).unwrap());
+    }
+    // Weights written for another N, or corrupted weights, produce an Err
+    assert!(serde_json::from_str::<NameExperiments<2>>(&json).is_err());
+    let corrupt = json.replacen("[0,", "[", 1);
+    assert!(serde_json::from_str::<NameExperiments<3>>(&corrupt).is_err());
+    let corrupt = json.replacen("\"name_sizes\":[[0", "\"name_sizes\":[[5", 1);
+    assert!(serde_json::from_str::<NameExperiments<3>>(&corrupt).is_err());
+}
+
+#[test]
+fn it_round_trips_weights_through_bytes() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    name_guess_experiments.train_negative_stream(NOT_NAMES.join("\n").as_bytes()).unwrap();
+    let bytes = name_guess_experiments.export_weights();
+    let mut restored: NameExperiments<3> = NameExperiments::import_weights(&bytes).unwrap();
+    assert_eq!(restored.positive_char_samples.weights, name_guess_experiments.positive_char_samples.weights);
+    assert_eq!(restored.negative_char_type_samples.sum, name_guess_experiments.negative_char_type_samples.sum);
+    assert_eq!(restored.name_sizes, name_guess_experiments.name_sizes);
+    name_guess_experiments.seed(21);
+    restored.seed(21);
+    for _ in 0..10 {
+        assert_eq!(restored.build_random_name(Some(16)).unwrap(), name_guess_experiments.build_random_name(Some(16)).unwrap());
+    }
+    // Weights written for another N, truncated weights or trailing bytes produce an Err
+    assert!(NameExperiments::<2>::import_weights(&bytes).is_err_and(|e| e.contains("N=3")));
+    assert!(NameExperiments::<3>::import_weights(&bytes[..bytes.len() - 1]).is_err());
+    let mut padded = bytes.clone();
+    padded.push(0);
+    assert!(NameExperiments::<3>::import_weights(&padded).is_err());
+    assert!(NameExperiments::<3>::import_weights(&[]).is_err());
+}
+
+#[test]
+fn it_builds_the_same_names_from_identically_seeded_generators() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_GOBLIN_NAMES.join("\n").as_bytes()).unwrap();
+    let mut first = fastrand::Rng::with_seed(5);
+    let mut second = fastrand::Rng::with_seed(5);
+    for _ in 0..10 {
+        assert_eq!(name_guess_experiments.build_random_name_seeded(Some(16), &mut first).unwrap(), name_guess_experiments.build_random_name_seeded(Some(16), &mut second).unwrap());
+    }
+    let char_seq = [ValidChar::null; 3];
+    let char_type_seq = [CharType::Null; 3];
+    for _ in 0..10 {
+        assert_eq!(
+            name_guess_experiments.guess_next_char_seeded(&char_seq, &char_type_seq, 0, &mut first).unwrap(),
+            name_guess_experiments.as_inference().guess_next_char_seeded(&char_seq, &char_type_seq, 0, &mut second).unwrap()
+        );
+    }
+}
+
+/// A `RandomSource` stepping evenly through `[0, 1)`, counting how often it was drawn from
+struct SteppingSource {
+    next: f64,
+    draws: usize,
+}
+
+impl RandomSource for SteppingSource {
+    fn next_f64(&mut self) -> f64 {
+        self.draws += 1;
+        self.next = (self.next + 0.381966) % 1.0;
+        self.next
+    }
+}
+
+#[test]
+fn it_picks_characters_from_a_custom_random_source() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_GOBLIN_NAMES.join("\n").as_bytes()).unwrap();
+    let mut first = SteppingSource { next: 0.0, draws: 0 };
+    let mut second = SteppingSource { next: 0.0, draws: 0 };
+    for _ in 0..10 {
+        let name = name_guess_experiments.build_random_name_seeded(Some(16), &mut first).unwrap();
+        assert_eq!(name, name_guess_experiments.as_inference().build_random_name_seeded(Some(16), &mut second).unwrap());
+    }
+    // At least one draw for every character and terminator
+    assert!(first.draws >= 10);
+    assert_eq!(first.draws, second.draws);
+}
+
+#[test]
+fn it_counts_past_u8_with_a_wider_counter() {
+    let corpus = "grok\n".repeat(300);
+    let context = [ValidChar::g, ValidChar::r];
+    let mut narrow: NameExperiments<2> = NameExperiments::new();
+    narrow.train_positive_stream(corpus.as_bytes()).unwrap();
+    assert_eq!(narrow.positive_char_samples.get_row(&context).unwrap()[ValidChar::o as usize], u8::MAX);
+    let mut wide: NameExperiments<2, NGramWeights<2, { ValidChar::VARIANTCOUNT as usize }, u16>> = NameExperiments::new();
+    assert_eq!(wide.train_positive_stream(corpus.as_bytes()).unwrap(), 300);
+    assert_eq!(wide.positive_char_samples.get_row(&context).unwrap()[ValidChar::o as usize], 300);
+    assert_eq!(wide.positive_char_samples.sum(&context).unwrap(), 300);
+    let char_type_seq = CharType::classify_all(&context).unwrap();
+    let (probabilities, sum, _) = wide.generate_probability_distribution(&context, &char_type_seq, 2, None, None, Some(false), None).unwrap();
+    assert!(sum > 0.0 && sum.is_finite());
+    let most_probable = (0..probabilities.len()).max_by(|&i, &j| probabilities[i].total_cmp(&probabilities[j])).unwrap();
+    assert_eq!(ValidChar::ALLCHARS[most_probable], ValidChar::o);
+    assert!(wide.build_random_name_with_strategy(Some(16), SamplingStrategy::Greedy).unwrap().starts_with("grok"));
+}
+
+#[test]
+fn it_saturates_weights_instead_of_failing_to_read() {
+    let mut name_guess_experiments: NameExperiments<2> = NameExperiments::new();
+    let sample: Vec<Option<char>> = "ab".chars().map(Some).chain([None]).collect();
+    for _ in 0..300 {
+        name_guess_experiments.read_positive_sample(&sample).unwrap();
+    }
+    assert_eq!(name_guess_experiments.total_positive_samples(), 300);
+    let context = [ValidChar::null, ValidChar::a];
+    assert_eq!(name_guess_experiments.positive_char_samples.get_row(&context).unwrap()[ValidChar::b as usize], u8::MAX);
+    assert_eq!(name_guess_experiments.positive_char_samples.sum(&context).unwrap(), u8::MAX as usize);
+    // The length histogram is not capped
+    assert_eq!(name_guess_experiments.name_sizes.0[2], 300);
+    assert!(name_guess_experiments.build_random_name(Some(16)).is_ok());
+}
+
+#[test]
+fn it_folds_accented_letters_into_their_base_letters() {
+    let accented = ["Zoë", "José", "Añez", "Müller", "Søren", "Åsa", "Łukasz", "Dvořák", "Çelik", "Ólafur"];
+    let folded = ["zoe", "jose", "anez", "muller", "soren", "asa", "lukasz", "dvorak", "celik", "olafur"];
+    for (name, expected) in accented.iter().zip(folded) {
+        let chars: String = name.chars().map(|c| char::from(ValidChar::try_from(&c).unwrap())).collect();
+        assert_eq!(chars, expected);
+    }
+    // Letters without a single base letter are still rejected
+    assert!(ValidChar::try_from(&'æ').is_err());
+    let mut name_guess_experiments: NameExperiments<2> = NameExperiments::new();
+    let name = Name::<8>::new("José", "", name::PaddingBias::Left, None, None, None, None);
+    name_guess_experiments.read_positive_sample(&name.text).unwrap();
+    assert_eq!(name_guess_experiments.positive_char_samples.get_row(&[ValidChar::s, ValidChar::e]).unwrap()[ValidChar::null as usize], 1);
+    assert_eq!(name_guess_experiments.capitalization_templates(), &[(vec![0], 1)]);
+}
+
+#[test]
+fn it_limits_an_experiment_to_an_alphabet() {
+    let alphabet: Vec<char> = "aeioubdghklmnrstz'".chars().collect();
+    let mut name_guess_experiments: NameExperiments<3, AlphabetWeights<3>> = NameExperiments::with_alphabet(&alphabet).unwrap();
+    assert_eq!(name_guess_experiments.positive_char_samples.row_count(), (alphabet.len() + 1).pow(3));
+    assert_eq!(name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap(), INPUT_ORC_NAMES.len());
+    for _ in 0..50 {
+        let new_name = name_guess_experiments.build_random_name(Some(16)).unwrap();
+        assert!(new_name.chars().all(|c| alphabet.contains(&c)), "{new_name} is not made up of the alphabet");
+    }
+    assert!(NameExperiments::<3, AlphabetWeights<3>>::with_alphabet(&['a', 'b', 'A']).is_err());
+    assert!(NameExperiments::<3, AlphabetWeights<3>>::with_alphabet(&['a', '7']).is_err());
+    assert!(NameExperiments::<3, AlphabetWeights<3>>::with_alphabet(&['a', '_']).is_err());
+}
+
+#[test]
+fn it_limits_the_weights_of_a_label_to_the_alphabet() {
+    let alphabet = ['a', 'b', 'd', 'e', 'g', 'r'];
+    let mut name_guess_experiments: NameExperiments<3, AlphabetWeights<3>> = NameExperiments::with_alphabet(&alphabet).unwrap();
+    for text in ["Qazbeg", "Zaqreb", "Garqaz", "Bezqad", "Dazgeq", "Regzaq"] {
+        let text: Vec<Option<char>> = text.chars().map(Some).chain([None]).collect();
+        name_guess_experiments.read_positive_sample_for(&text, "x").unwrap();
+    }
+    assert_eq!(name_guess_experiments.labeled_experiments["x"].positive_char_samples.row_count(), (alphabet.len() + 1).pow(3));
+    for _ in 0..50 {
+        let new_name = name_guess_experiments.build_random_name_for("x", Some(16)).unwrap();
+        assert!(new_name.chars().all(|c| alphabet.contains(&c)), "{new_name} is not made up of the alphabet");
+    }
+}
+
+#[test]
+fn it_builds_a_name_with_copied_labels() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let labels: Name<1> = Name::new("", "m", name::PaddingBias::Left, Some("orc"), Some("mountain"), Some("fear"), None);
+    for _ in 0..20 {
+        let new_name: Name<8> = name_guess_experiments.build_random_name_labeled(Some(16), &labels).unwrap();
+        let text: String = new_name.text.iter().map_while(|&c| c).collect();
+        assert!(!text.is_empty() && text.len() <= 7);
+        assert!(new_name.text[text.len()..].iter().all(|c| c.is_none()));
+        assert_eq!(new_name.gender_identity, labels.gender_identity);
+        assert_eq!(new_name.major_culture_label, labels.major_culture_label);
+        assert_eq!(new_name.minor_culture_label, labels.minor_culture_label);
+        assert_eq!(new_name.sentiment_label, labels.sentiment_label);
+        assert_eq!(new_name.family_label, None);
+    }
+    assert!(name_guess_experiments.as_inference().build_random_name_labeled::<1, 1>(None, &labels).is_err());
+}
+
+#[test]
+fn it_scores_names_by_log_probability() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let to_text = |name: &str| -> Vec<Option<char>> {name.chars().map(Some).chain([None]).collect()};
+    let familiar = name_guess_experiments.score_name(&to_text(INPUT_ORC_NAMES[0])).unwrap();
+    let unfamiliar = name_guess_experiments.score_name(&to_text("xqzvw")).unwrap();
+    assert!(familiar < 0.0 && unfamiliar < familiar);
+    let right_padded = Name::<16>::new(INPUT_ORC_NAMES[0], "", name::PaddingBias::Right, None, None, None, None);
+    assert_eq!(name_guess_experiments.score_name(&right_padded.text).unwrap(), familiar);
+    // Paths that only differ by syllable breaks are summed by top_names, so the direct path is at most as probable
+    let (top_name, top_probability) = name_guess_experiments.top_names(1, 16).unwrap().remove(0);
+    let top_score = name_guess_experiments.as_inference().score_name(&to_text(&top_name)).unwrap();
+    assert!(top_score <= top_probability.ln() + 1e-9);
+    assert!(top_score > top_probability.ln() - 0.5);
+    // Invalid characters are scored as null rather than producing an Err
+    assert!(name_guess_experiments.score_name(&to_text("gr7k")).is_ok());
+}
+
+#[test]
+fn it_measures_perplexity_over_held_out_names() {
+    let to_text = |name: &str| -> Vec<Option<char>> {name.chars().map(Some).chain([None]).collect()};
+    let (training, held_out) = INPUT_ORC_NAMES.split_at(INPUT_ORC_NAMES.len() - 5);
+    let held_out: Vec<Vec<Option<char>>> = held_out.iter().map(|name| to_text(name)).collect();
+    let held_out: Vec<&[Option<char>]> = held_out.iter().map(|text| text.as_slice()).collect();
+    let mut untrained: NameExperiments<2> = NameExperiments::new();
+    untrained.train_positive_stream("a".as_bytes()).unwrap();
+    let mut trained: NameExperiments<2> = NameExperiments::new();
+    trained.train_positive_stream(training.join("\n").as_bytes()).unwrap();
+    let trained_perplexity = trained.perplexity(&held_out).unwrap();
+    assert!(trained_perplexity.is_finite() && trained_perplexity >= 1.0);
+    assert!(trained_perplexity < untrained.perplexity(&held_out).unwrap());
+    // A single name matches its own score
+    let score = trained.score_name(held_out[0]).unwrap();
+    let chars = held_out[0].iter().map_while(|&c| c).count() + 1;
+    assert!((trained.perplexity(&held_out[..1]).unwrap() - (-score / chars as f64).exp()).abs() < 1e-9);
+    // Invalid characters do not produce an Err
+    assert!(trained.perplexity(&[&to_text("gr7k")]).is_ok());
+    // Generation never ends a name before the shortest length read, so a shorter name can not be scored finitely
+    assert_eq!(trained.perplexity(&[&to_text("Grok")]).unwrap(), f64::INFINITY);
+    assert!(trained.perplexity(&[]).is_err());
+}
+
+#[test]
+fn it_approaches_the_most_probable_name_at_a_low_temperature() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    // Exactly tied characters stay tied at any temperature, so the most probable path is made clear
+    name_guess_experiments.train_positive_stream("grok\ngrok\ngrok\ngrim\nzug\nmoga\nbrak\nurgash".as_bytes()).unwrap();
+    let most_probable = name_guess_experiments.build_random_name_with_strategy(Some(16), SamplingStrategy::Greedy).unwrap();
+    name_guess_experiments.set_generation_config(GenerationConfig { temperature: 0.001, ..Default::default() });
+    name_guess_experiments.seed(3);
+    for _ in 0..20 {
+        assert_eq!(name_guess_experiments.build_random_name(Some(16)).unwrap(), most_probable);
+    }
+    // A high temperature flattens the distribution into more varied names
+    name_guess_experiments.set_generation_config(GenerationConfig { temperature: 3.0, ..Default::default() });
+    let names: HashSet<String> = (0..20).map(|_| name_guess_experiments.build_random_name(Some(16)).unwrap()).collect();
+    assert!(names.len() > 1);
+    name_guess_experiments.set_generation_config(GenerationConfig { temperature: 0.0, ..Default::default() });
+    assert!(name_guess_experiments.build_random_name(Some(16)).is_err());
+}
+
+#[test]
+fn it_never_generates_names_below_the_minimum_length() {
+    let mut name_guess_experiments: NameExperiments<2> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("a\nbo\nki\nzu\nmo\nug\ne".as_bytes()).unwrap();
+    name_guess_experiments.set_generation_config(GenerationConfig { min_length: Some(4), ..Default::default() });
+    name_guess_experiments.seed(11);
+    for _ in 0..50 {
+        let new_name = name_guess_experiments.build_random_name(Some(16)).unwrap();
+        assert!(new_name.len() >= 4, "{new_name} is shorter than 4");
+    }
+    // The hard stop still applies
+    name_guess_experiments.set_generation_config(GenerationConfig { min_length: Some(20), ..Default::default() });
+    assert!(name_guess_experiments.build_random_name(Some(6)).unwrap().len() <= 6);
+}
+
+#[test]
+fn it_dedupes_a_batch_of_names() {
+    let texts = ["Grukthar", "Morgash", " grukthar", "MORGASH ", "Nargul", "", "Grukthar"];
+    let (names, duplicates) = Name::<16>::new_from_batch_deduped(&texts, "m", name::PaddingBias::Left, Some("orc"), None, None, None);
+    assert_eq!(names.len(), 3);
+    assert_eq!(duplicates, 3);
+    let first: String = names[0].text.iter().map_while(|&c| c).collect();
+    assert_eq!(first, "Grukthar");
+    let last: String = names[2].text.iter().map_while(|&c| c).collect();
+    assert_eq!(last, "Nargul");
+    assert!(names.iter().all(|name| name.major_culture_label == names[0].major_culture_label));
+}
+
+#[test]
+fn it_reads_from_a_reader_and_handles_unmappable_lines() {
+    let corpus = format!("{}\nZug7\n\nGrük\n", INPUT_ORC_NAMES.join("\n"));
+    let mut skipping: NameExperiments<3> = NameExperiments::new();
+    let read = skipping.read_positive_from_reader(corpus.as_bytes(), true).unwrap();
+    assert_eq!(read, INPUT_ORC_NAMES.len() + 1);
+    assert_eq!(skipping.total_positive_samples(), read);
+
+    let mut strict: NameExperiments<3> = NameExperiments::new();
+    assert!(strict.read_positive_from_reader(corpus.as_bytes(), false).is_err_and(|e| e.contains(&format!("Line {}", INPUT_ORC_NAMES.len() + 1))));
+    assert_eq!(strict.total_positive_samples(), INPUT_ORC_NAMES.len());
+}
+
+#[test]
+fn it_produces_an_err_for_an_unusable_n() {
+    assert!(NameExperiments::<1>::try_new().is_err_and(|e| e.contains("at least 2")));
+    assert!(NameExperiments::<20>::try_new().is_err_and(|e| e.contains("overflow")));
+    assert!(<NGramWeights<20, 31> as WeightStore<31>>::try_new().is_err_and(|e| e.contains("overflow")));
+    assert!(NameExperiments::<2>::try_new().is_ok());
+}
+
+#[test]
+fn it_measures_name_limits_in_characters() {
+    // Five characters, but ten bytes
+    let text = "Ükøéñ";
+    assert!(text.len() > 7);
+    let name = Name::<8>::try_new(text, "m", name::PaddingBias::Right, None, None, None, None).unwrap();
+    let read: String = name.text.iter().flatten().collect();
+    assert_eq!(read, text);
+    assert_eq!(Name::<8>::new(text, "m", name::PaddingBias::Left, None, None, None, None).text[..5], name.text[3..]);
+    assert!(Name::<5>::try_new(text, "m", name::PaddingBias::Left, None, None, None, None).is_err_and(|e| e.contains("text") && e.contains("4")));
+    assert!(Name::<8>::try_new("Grok", "ééééééééééééééééé", name::PaddingBias::Left, None, None, None, None).is_err_and(|e| e.contains("gender_ident") && e.contains("16")));
+    assert!(Name::<8>::try_new("Grok", "éééééééééééééééé", name::PaddingBias::Left, None, None, None, None).is_ok());
+}
+
+#[test]
+fn it_builds_batches_of_names() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    assert_eq!(name_guess_experiments.build_random_names(20, Some(12)).unwrap().len(), 20);
+
+    let distinct = name_guess_experiments.build_distinct_names(20, 2000, Some(12)).unwrap();
+    assert_eq!(distinct.len(), 20);
+    assert_eq!(distinct.iter().collect::<HashSet<_>>().len(), 20);
+
+    // The attempts run out before the batch is complete, so it comes up short rather than failing
+    let short = name_guess_experiments.build_distinct_names(50, 10, Some(12)).unwrap();
+    assert!(!short.is_empty() && short.len() <= 10);
+    assert_eq!(short.iter().collect::<HashSet<_>>().len(), short.len());
+}
+
+#[test]
+fn it_never_regurgitates_a_seen_name() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("Grok\nMog".as_bytes()).unwrap();
+    let seen: HashSet<String> = ["Grok".to_string(), "Mog".to_string()].into_iter().collect();
+    for _ in 0..200 {
+        let name = name_guess_experiments.build_novel_name(&seen, 1000, Some(8)).unwrap();
+        assert!(name != "grok" && name != "mog", "{name} was read while training");
+    }
+    assert!(name_guess_experiments.build_novel_name(&seen, 0, Some(8)).is_err());
+}
+
+#[test]
+fn it_iterates_over_generated_names() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let names: Vec<String> = name_guess_experiments.names(Some(12)).take(10).collect::<Result<_, _>>().unwrap();
+    assert_eq!(names.len(), 10);
+    assert!(names.iter().all(|name| name.chars().count() <= 12));
+    let long_names: Vec<String> = name_guess_experiments.names(Some(12)).filter_map(Result::ok).filter(|name| name.len() > 5).take(5).collect();
+    assert!(long_names.iter().all(|name| name.len() > 5));
+}
+
+#[test]
+fn it_backs_off_to_shorter_contexts_when_a_context_is_rare() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    // "qra" is never read, but "ra" is read often
+    let char_seq = [ValidChar::q, ValidChar::r, ValidChar::a];
+    let char_type_seq = char_seq.map(CharType::default_for);
+    let sharpest_share = |experiments: &NameExperiments<3>| {
+        let (probabilities, sum, _) = experiments.generate_probability_distribution(&char_seq, &char_type_seq, 3, None, None, None, Some(true)).unwrap();
+        let (index, max) = probabilities.iter().enumerate().fold((0, 0.0), |best, (i, &p)| if p > best.1 {(i, p)} else {best});
+        (ValidChar::ALLCHARS[index], max / sum)
+    };
+    let (_, eased_share) = sharpest_share(&name_guess_experiments);
+    let start = [ValidChar::null; 3];
+    let (eased_start, _, _) = name_guess_experiments.generate_probability_distribution(&start, &[CharType::Null; 3], 0, None, None, None, None).unwrap();
+
+    name_guess_experiments.set_generation_config(GenerationConfig { backoff_threshold: 10, ..Default::default() });
+    let (backed_off_char, backed_off_share) = sharpest_share(&name_guess_experiments);
+    assert!(backed_off_share > 2.0 * eased_share, "{backed_off_share} is not much sharper than {eased_share}");
+    let (ra_row, _) = name_guess_experiments.positive_observations(&[ValidChar::null, ValidChar::r, ValidChar::a]).unwrap();
+    assert!(ra_row[backed_off_char as usize] > 0.0, "{backed_off_char:?} never followed \"ra\"");
+    // A context that is observed often enough is left as it is
+    let (backed_off_start, _, _) = name_guess_experiments.generate_probability_distribution(&start, &[CharType::Null; 3], 0, None, None, None, None).unwrap();
+    assert_eq!(eased_start, backed_off_start);
+}
+
+#[test]
+fn it_interpolates_over_every_context_length() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let char_seq = [ValidChar::q, ValidChar::r, ValidChar::a];
+    let char_type_seq = char_seq.map(CharType::default_for);
+    let lambdas = [0.1, 0.2, 0.3, 0.4];
+    let mixed = name_guess_experiments.positive_probabilities(&char_seq, &[1.0; ValidChar::VARIANTCOUNT as usize], Some(&lambdas)).unwrap();
+    assert!((mixed.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+
+    let (interpolated, interpolated_sum, _) = name_guess_experiments.generate_probability_distribution_interpolated(&char_seq, &char_type_seq, 3, None, None, Some(false), None, &lambdas).unwrap();
+    assert!((interpolated.iter().sum::<f64>() - interpolated_sum).abs() < 1e-9);
+    // Weighting only the full context is the plain distribution
+    let (full_context, _, _) = name_guess_experiments.generate_probability_distribution_interpolated(&char_seq, &char_type_seq, 3, None, None, Some(false), None, &[0.0, 0.0, 0.0, 1.0]).unwrap();
+    let (plain, _, _) = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 3, None, None, Some(false), None).unwrap();
+    assert_eq!(full_context, plain);
+
+    for lambdas in [&[0.5, 0.5, 0.5, 0.5][..], &[-0.5, 0.5, 0.5, 0.5], &[0.5, 0.5], &[f64::NAN, 0.5, 0.5, 0.0]] {
+        assert!(name_guess_experiments.generate_probability_distribution_interpolated(&char_seq, &char_type_seq, 3, None, None, None, None, lambdas).is_err());
+    }
+}
+
+#[test]
+fn it_ranks_the_most_probable_next_characters() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let char_seq = [ValidChar::null, ValidChar::g, ValidChar::r];
+    let char_type_seq = char_seq.map(CharType::default_for);
+    let top = name_guess_experiments.top_k_next(&char_seq, &char_type_seq, 2, 5).unwrap();
+    assert_eq!(top.len(), 5);
+    assert!(top.windows(2).all(|pair| pair[0].1 > pair[1].1 || (pair[0].1 == pair[1].1 && (pair[0].0 as usize) < (pair[1].0 as usize))));
+    assert_eq!(top, name_guess_experiments.top_k_next(&char_seq, &char_type_seq, 2, 5).unwrap());
+    let everything = name_guess_experiments.top_k_next(&char_seq, &char_type_seq, 2, usize::MAX).unwrap();
+    assert!((everything.iter().map(|(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-9);
+    assert_eq!(everything[..5], top[..]);
+}
+
+#[test]
+fn it_keys_a_distribution_by_char() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let char_seq = [ValidChar::null, ValidChar::g, ValidChar::r];
+    let char_type_seq = char_seq.map(CharType::default_for);
+    let map = name_guess_experiments.distribution_as_map(&char_seq, &char_type_seq, 2, None, None, None, None).unwrap();
+    assert_eq!(map.len(), ValidChar::VARIANTCOUNT as usize);
+    assert!((map.values().sum::<f64>() - 1.0).abs() < 1e-9);
+    let (probabilities, sum, _) = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 2, None, None, None, None).unwrap();
+    assert_eq!(map[&'a'], probabilities[ValidChar::a as usize] / sum);
+    assert_eq!(map[&'\0'], probabilities[ValidChar::null as usize] / sum);
+}
+
+#[test]
+fn it_resets_to_match_a_fresh_experiment() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_GOBLIN_NAMES.join("\n").as_bytes()).unwrap();
+    name_guess_experiments.train_negative_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    name_guess_experiments.reset();
+    for char_seq in [[ValidChar::null; 3], [ValidChar::null, ValidChar::g, ValidChar::r], [ValidChar::z; 3]] {
+        let (row, sum) = name_guess_experiments.positive_char_samples.get_row_and_sum(&char_seq).unwrap();
+        assert!(row.iter().all(|&weight| weight == 0) && sum == 0);
+        let (row, sum) = name_guess_experiments.negative_char_samples.get_row_and_sum(&char_seq).unwrap();
+        assert!(row.iter().all(|&weight| weight == 0) && sum == 0);
+    }
+    assert_eq!(name_guess_experiments.total_positive_samples(), 0);
+
+    let mut fresh: NameExperiments<3> = NameExperiments::new();
+    fresh.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    assert_eq!(name_guess_experiments.export_weights(), fresh.export_weights());
+}
+
+#[test]
+fn it_decays_weights_monotonically_towards_zero() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(format!("{}\n", INPUT_ORC_NAMES.join("\n")).repeat(3).as_bytes()).unwrap();
+    assert!(name_guess_experiments.decay(0.0).is_err());
+    assert!(name_guess_experiments.decay(1.5).is_err());
+    let mut previous = name_guess_experiments.positive_char_samples.clone();
+    let mut rounds = 0;
+    while previous.sum.iter().any(|&sum| sum > 0) {
+        name_guess_experiments.decay(0.3).unwrap();
+        let current = &name_guess_experiments.positive_char_samples;
+        for (index, (row, previous_row)) in current.weights.iter().zip(previous.weights.iter()).enumerate() {
+            for (&weight, &previous_weight) in row.iter().zip(previous_row.iter()) {
+                assert!(weight <= previous_weight);
+                assert!(previous_weight <= 1 || weight > 0, "A weight of {previous_weight} vanished in one decay");
+            }
+            assert_eq!(current.sum[index], row.iter().map(|&weight| weight as usize).sum::<usize>());
+        }
+        previous = current.clone();
+        rounds += 1;
+        assert!(rounds < 20, "Weights never decayed to zero");
+    }
+    let (histogram, total) = &name_guess_experiments.name_sizes;
+    assert_eq!(*total, histogram.iter().sum::<usize>());
+}
+
+#[test]
+fn it_eases_weights_without_reordering_them() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let before = name_guess_experiments.positive_char_samples.clone();
+    let mut eased = before.clone();
+    assert!(eased.apply_easing(1, 0).is_err());
+    eased.apply_easing(1, 2).unwrap();
+    for ((row, eased_row), &eased_sum) in before.weights.iter().zip(eased.weights.iter()).zip(eased.sum.iter()) {
+        for (i, j) in (0..row.len()).flat_map(|i| (0..row.len()).map(move |j| (i, j))) {
+            assert_eq!(row[i].cmp(&row[j]), eased_row[i].cmp(&eased_row[j]));
+        }
+        assert_eq!(eased_sum, eased_row.iter().map(|&weight| weight as usize).sum::<usize>());
+    }
+    // Half a pseudo-count is added, rather than rounding up to a whole observation of every character
+    let start = [ValidChar::null; 3];
+    let (row, sum) = before.get_row_and_sum(&start).unwrap();
+    let (eased_row, eased_sum) = eased.get_row_and_sum(&start).unwrap();
+    for (&weight, &eased_weight) in row.iter().zip(eased_row.iter()) {
+        let expected = (weight as f64 + 0.5) / (sum as f64 + 0.5 * ValidChar::VARIANTCOUNT as f64);
+        assert!((eased_weight as f64 / eased_sum as f64 - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn it_ends_names_at_the_lengths_that_were_read() {
+    // Two names of 3 letters and two names of 5 letters
+    let name_sizes = (vec![0, 0, 0, 2, 0, 2], 4);
+    for (character_count, expected) in [(0, 0.0), (2, 0.0), (3, 0.5), (4, 0.5), (5, 1.0), (9, 1.0)] {
+        assert_eq!(crate::termination_probability(&name_sizes, character_count), expected, "at {character_count} characters");
+    }
+    // A histogram whose total disagrees with its counts is clamped
+    assert_eq!(crate::termination_probability(&(vec![0, 3], 2), 1), 1.0);
+
+    let mut name_guess_experiments: NameExperiments<2> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("abc\nabc\nabcde\nabcde".as_bytes()).unwrap();
+    let null_and_continuing = |char_seq: [ValidChar; 2], character_count: usize| {
+        let (probabilities, _, _) = name_guess_experiments.generate_probability_distribution(&char_seq, &char_seq.map(CharType::default_for), character_count, None, None, Some(false), None).unwrap();
+        (probabilities[ValidChar::null as usize], probabilities[..ValidChar::null as usize].iter().sum::<f64>())
+    };
+    let (null, continuing) = null_and_continuing([ValidChar::a, ValidChar::b], 2);
+    assert!(null == 0.0 && continuing > 0.0);
+    let (null, continuing) = null_and_continuing([ValidChar::b, ValidChar::c], 3);
+    assert!(null > 0.0 && continuing > 0.0);
+    let (null, continuing) = null_and_continuing([ValidChar::d, ValidChar::e], 5);
+    assert!(null > 0.0 && continuing == 0.0);
+    // Names never run past the longest length read
+    for _ in 0..50 {
+        let length = name_guess_experiments.build_random_name(None).unwrap().len();
+        assert!(length <= 5, "A name of {length} letters was generated");
+    }
+}
+
+#[test]
+fn it_produces_an_err_rather_than_picking_from_an_all_zero_distribution() {
+    let mut name_guess_experiments: NameExperiments<2> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream("Morgash\nNargul".as_bytes()).unwrap();
+    name_guess_experiments.train_negative_stream("zzzz".as_bytes()).unwrap();
+    // "zz" was never read positively, and every character that followed it negatively is ruled out
+    name_guess_experiments.set_generation_config(GenerationConfig { negative_influence: 1e6, ..Default::default() });
+    let char_seq = [ValidChar::z, ValidChar::z];
+    let char_type_seq = char_seq.map(CharType::default_for);
+    let (_, sum, _) = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 2, None, None, None, None).unwrap();
+    assert_eq!(sum, 0.0);
+    for strategy in [SamplingStrategy::Proportional, SamplingStrategy::Uniform(3), SamplingStrategy::Greedy] {
+        let guess = name_guess_experiments.guess_next_char_with_strategy(&char_seq, &char_type_seq, 2, strategy);
+        assert!(guess.is_err(), "{strategy:?} guessed {guess:?}");
+    }
+    // A pick of exactly 0.0 lands on the first possible character, not on an impossible one
+    let mut zero_source = SteppingSource { next: -0.381966, draws: 0 };
+    assert_eq!(SamplingStrategy::Proportional.pick(&[0.0, 0.0, 2.0, 1.0], 3.0, &mut zero_source), Ok(2));
+    assert!(SamplingStrategy::Proportional.pick(&[0.0; 4], 0.0, &mut zero_source).is_err());
+}
+
+#[test]
+fn it_counts_characters_rather_than_bytes_while_generating() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    // Every name is 6 characters long, counting its apostrophe or dash
+    name_guess_experiments.train_positive_stream("Gr'akk\nMor-ga\nUr-zog\nKa'gar".as_bytes()).unwrap();
+    let greedy_name = name_guess_experiments.build_random_name_with_strategy(None, SamplingStrategy::Greedy).unwrap();
+    assert_eq!(greedy_name, "gr'akk");
+    // A trailing space is trimmed once a name ends, so a name can fall short of 6 characters but never run past them
+    for _ in 0..50 {
+        let new_name = name_guess_experiments.build_random_name(None).unwrap();
+        assert!(new_name.chars().count() <= 6, "{new_name} ran past the longest length read");
+    }
+    // Generation stops at the hard stop in characters
+    assert!(name_guess_experiments.build_random_names(20, Some(4)).unwrap().iter().all(|name| name.chars().count() <= 4));
+}
+
+#[test]
+fn it_generates_from_the_weights_of_a_label() {
+    let female: Vec<Name<16>> = Name::new_from_batch(&["Elina", "Alani", "Nalia", "Lenia", "Ilena", "Anela"], "female", name::PaddingBias::Left, Some("Elf"), None, None, None);
+    let male: Vec<Name<16>> = Name::new_from_batch(&["Grukor", "Orgruk", "Korgur", "Rugrok", "Gorruk", "Urkog"], "male", name::PaddingBias::Left, Some("Elf"), None, None, None);
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.set_generation_config(GenerationConfig { epsilon: 0.05, ..Default::default() });
+    for n in female.iter().chain(male.iter()) {
+        let gender: String = n.gender_identity.iter().flatten().collect();
+        name_guess_experiments.read_positive_sample_for(&n.text, &gender).unwrap();
+    }
+    assert_eq!(name_guess_experiments.total_positive_samples(), 12);
+    assert_eq!(name_guess_experiments.labels().collect::<Vec<&str>>(), vec!["female", "male"]);
+    let share_of_letters = |label: &str, letters: &str| -> f64 {
+        let names = (0..200).map(|_| name_guess_experiments.build_random_name_for(label, Some(16)).unwrap()).collect::<String>();
+        names.chars().filter(|&c| letters.contains(c)).count() as f64 / names.chars().count() as f64
+    };
+    // Each label favours its own letters, which the weights of the whole experiment would mix
+    assert!(share_of_letters("female", "aeiln") > 2.0 * share_of_letters("male", "aeiln"));
+    assert!(share_of_letters("male", "gkoru") > 2.0 * share_of_letters("female", "gkoru"));
+    // A seeded experiment also seeds the names of its labels
+    name_guess_experiments.seed(11);
+    let seeded: Vec<String> = (0..5).map(|_| name_guess_experiments.build_random_name_for("female", Some(16)).unwrap()).collect();
+    name_guess_experiments.seed(11);
+    assert_eq!(seeded, (0..5).map(|_| name_guess_experiments.build_random_name_for("female", Some(16)).unwrap()).collect::<Vec<String>>());
+    name_guess_experiments.reset();
+    assert!(name_guess_experiments.build_random_name_for("female", Some(16)).is_err());
+}
+
+// Covers the broad classification, which the `articulation` feature subdivides
+#[cfg(not(feature = "articulation"))]
+#[test]
+fn it_classifies_trills_and_flip_taps() {
+    let name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    assert_eq!(
+        name_guess_experiments.char_types_of("Rodrigo").unwrap(),
+        vec![CharType::Approximant, CharType::VowelRoot, CharType::FlipTap, CharType::Approximant, CharType::VowelRoot, CharType::Plosive, CharType::VowelRoot]
+    );
+    assert_eq!(name_guess_experiments.char_types_of("Barra").unwrap()[2..4], [CharType::Trill, CharType::Trill]);
+    // A 't' or 'd' after a consonant is still a plosive
+    assert_eq!(name_guess_experiments.char_types_of("Elda").unwrap()[2], CharType::Plosive);
+    assert_eq!(name_guess_experiments.char_types_of("Grukthar").unwrap()[4..], [CharType::Plosive, CharType::Fricative, CharType::VowelRoot, CharType::Trill]);
+    // The character type weights are sized for every variant
+    assert_eq!(name_guess_experiments.positive_char_type_samples.weights.len(), 13usize.pow(3));
+    let pair_experiments: NameExperiments<2> = NameExperiments::new();
+    assert_eq!(pair_experiments.negative_char_type_samples.weights.len(), 13usize.pow(2));
+}
+
+#[cfg(feature = "articulation")]
+#[test]
+fn it_subdivides_character_types_by_place_of_articulation() {
+    let name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    assert_eq!(
+        name_guess_experiments.char_types_of("Bogdan").unwrap(),
+        vec![CharType::LabialPlosive, CharType::VowelRoot, CharType::VelarPlosive, CharType::Plosive, CharType::VowelRoot, CharType::Nasal]
+    );
+    assert_eq!(
+        name_guess_experiments.char_types_of("Wyhm").unwrap(),
+        vec![CharType::LabialApproximant, CharType::VowelRoot, CharType::GlottalFricative, CharType::BilabialNasal]
+    );
+    assert_eq!(name_guess_experiments.char_types_of("Ingvar").unwrap()[2..4], [CharType::VelarNasal, CharType::LabiodentalFricative]);
+    // An 'h' that forms 'th' or 'sh' with the character before it is not glottal
+    assert_eq!(name_guess_experiments.char_types_of("Thash").unwrap(), vec![CharType::Plosive, CharType::Fricative, CharType::VowelRoot, CharType::Fricative, CharType::Fricative]);
+    assert_eq!(CharType::classify(&[ValidChar::y]), Ok(CharType::PalatalApproximant));
+    // Characters that are not consonants keep their broad classification
+    assert_eq!(CharType::classify(&[ValidChar::s, ValidChar::c]), Ok(CharType::Silent));
+    // The character type weights are sized for every variant
+    assert_eq!(name_guess_experiments.positive_char_type_samples.weights.len(), 21usize.pow(3));
+}
+
+#[test]
+fn it_counts_the_syllables_of_a_name() {
+    let syllables = |text: &str| -> usize {
+        let name: Name<24> = Name::new(text, "", name::PaddingBias::Left, None, None, None, None);
+        crate::count_syllables(&name.text)
+    };
+    for (text, expected) in [
+        ("Grukthar", 2), ("Grace", 1), ("Anne", 1), ("Strom", 1), ("Rodrigo", 3), ("Helena", 3), ("Mable", 2), ("Maya", 2),
+        ("Yvonne", 2), ("Rhys", 1), ("Aurelia", 3), ("Le", 1), ("Iron maul", 3), ("a|e", 2), ("Grr", 0), ("", 0),
+    ] {
+        assert_eq!(syllables(text), expected, "{text}");
+    }
+    // Right padded text and text after the first None are handled like reading a sample
+    assert_eq!(crate::count_syllables(&[Some('N'), Some('a'), Some('r'), Some('g'), Some('u'), Some('l'), None, Some('a')]), 2);
+}
+
+#[test]
+fn it_builds_names_with_a_number_of_syllables() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_GREEK_FEMALE_NAMES.join("\n").as_bytes()).unwrap();
+    let syllables_of = |name: &str| crate::count_syllables(&name.chars().map(Some).collect::<Vec<Option<char>>>());
+    let names: Vec<String> = (0..40).map(|_| name_guess_experiments.build_random_name_syllables(3, Some(24)).unwrap()).collect();
+    // A name that ends before the hard stop (less a trimmed trailing space) has reached its target and added no syllable since
+    let ended: Vec<&String> = names.iter().filter(|name| name.chars().count() < 23).collect();
+    assert!(ended.len() >= 20, "Only {} of {names:?} ended before the hard stop", ended.len());
+    assert!(ended.iter().all(|name| syllables_of(name) == 3), "{ended:?}");
+    assert!(names.iter().all(|name| syllables_of(name) <= 3), "{names:?}");
+    for _ in 0..20 {
+        assert_eq!(syllables_of(&name_guess_experiments.build_random_name_syllables(1, Some(24)).unwrap()), 1);
+    }
+    // The hard stop ends a name before it reaches the target
+    let short = name_guess_experiments.build_random_name_syllables(10, Some(5)).unwrap();
+    assert!(short.chars().count() <= 5 && syllables_of(&short) < 10);
+}
+
+#[test]
+fn it_describes_the_lengths_of_the_names_read() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    assert_eq!(name_guess_experiments.length_distribution(), vec![0.0]);
+    assert_eq!(name_guess_experiments.mean_length(), 0.0);
+    assert_eq!(name_guess_experiments.length_mode(), None);
+    name_guess_experiments.train_positive_stream("Zik\nNib\nGruk|thar\nMorgash".as_bytes()).unwrap();
+    name_guess_experiments.train_negative_stream("Xqzt".as_bytes()).unwrap();
+    assert_eq!(name_guess_experiments.length_distribution(), vec![0.0, 0.0, 0.0, 0.4, 0.2, 0.0, 0.0, 0.2, 0.2]);
+    assert!((name_guess_experiments.length_distribution().iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    assert!((name_guess_experiments.mean_length() - 5.0).abs() < 1e-12);
+    assert_eq!(name_guess_experiments.length_mode(), Some(3));
+    // Ties go to the shortest length
+    name_guess_experiments.train_positive_stream("Grukthor".as_bytes()).unwrap();
+    assert_eq!(name_guess_experiments.length_mode(), Some(3));
+}
+
+#[test]
+fn it_produces_an_err_for_invalid_easing_and_inconsistent_weights() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    name_guess_experiments.train_negative_stream("Xqzt".as_bytes()).unwrap();
+    let char_seq = [ValidChar::null; 3];
+    let char_type_seq = [CharType::Null; 3];
+    for (pos_easing_scale, neg_easing_scale) in [(Some(-0.5), None), (None, Some(-1.0)), (Some(f64::NAN), None), (None, Some(f64::INFINITY))] {
+        let distribution = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 0, pos_easing_scale, neg_easing_scale, None, None);
+        assert!(distribution.is_err_and(|e| e.contains("easing")), "{pos_easing_scale:?}, {neg_easing_scale:?}");
+    }
+    let mut pos_easing = [1.0; ValidChar::VARIANTCOUNT as usize];
+    pos_easing[ValidChar::q as usize] = -0.1;
+    let distribution = name_guess_experiments.generate_probability_distribution_with_easing(&char_seq, &char_type_seq, 0, &pos_easing, None, None, None);
+    assert!(distribution.is_err_and(|e| e.contains("for q")));
+    assert!(name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 0, Some(0.0), Some(0.0), None, None).is_ok());
+    // A row of negative weights that sums to less than one of its weights. Only possible if the weights are edited by hand
+    for sum in name_guess_experiments.negative_char_samples.sum.iter_mut() {
+        *sum = sum.saturating_sub(1);
+    }
+    let distribution = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 0, None, None, None, None);
+    assert!(distribution.is_err_and(|e| e.contains("inconsistent") && e.contains("x was observed 1 times")));
+}
+
+#[test]
+fn it_generates_after_saturating_the_negative_weights() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    // More observations of the same name than a u8 weight can count
+    let negative_corpus = vec!["Grukthar"; 300].join("\n");
+    assert_eq!(name_guess_experiments.train_negative_stream(negative_corpus.as_bytes()), Ok(300));
+    assert!(name_guess_experiments.negative_char_samples.weights.iter().flatten().any(|&weight| weight == u8::MAX));
+    // Dropped observations are dropped from the sum of the row too, so no weight is larger than its sum
+    for (row, sum) in name_guess_experiments.negative_char_samples.weights.iter().zip(name_guess_experiments.negative_char_samples.sum.iter()) {
+        assert_eq!(row.iter().map(|&weight| usize::from(weight)).sum::<usize>(), *sum);
+    }
+    let char_seq = [ValidChar::null; 3];
+    let char_type_seq = [CharType::Null; 3];
+    let (distribution, _, _) = name_guess_experiments.generate_probability_distribution(&char_seq, &char_type_seq, 0, None, None, None, None).unwrap();
+    assert!(distribution.iter().all(|p| p.is_finite() && *p >= 0.0));
+    for _ in 0..20 {
+        name_guess_experiments.build_random_name(None).unwrap();
+    }
+}
+
+#[test]
+fn it_builds_names_starting_with_a_filtered_character() {
+    let mut name_guess_experiments: NameExperiments<3> = NameExperiments::new();
+    name_guess_experiments.train_positive_stream(INPUT_ORC_NAMES.join("\n").as_bytes()).unwrap();
+    let vowels = [ValidChar::a, ValidChar::e, ValidChar::i, ValidChar::o, ValidChar::u];
+    for _ in 0..50 {
+        let name = name_guess_experiments.build_random_name_starting_with(Some(&vowels), None).unwrap();
+        assert!(name.starts_with(['a', 'e', 'i', 'o', 'u']), "{name} does not start with a vowel");
+    }
+    assert!(name_guess_experiments.build_random_name_starting_with(None, None).is_ok());
+    // Unobserved starts are eased rather than ruled out
+    assert!(name_guess_experiments.build_random_name_starting_with(Some(&[ValidChar::q]), None).is_ok_and(|name| name.starts_with('q')));
+    assert!(name_guess_experiments.build_random_name_starting_with(Some(&[]), None).is_err());
+    assert!(name_guess_experiments.build_random_name_starting_with(Some(&[ValidChar::null, ValidChar::syllable_break]), None).is_err());
+}
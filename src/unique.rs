@@ -0,0 +1,143 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{InferenceModel, NameExperiments};
+
+/// An endless supply of names that never repeats itself. Created with `NameExperiments::unique_name_stream`.
+///
+/// Every name yielded is remembered. A name that was already yielded is generated again, up to `UniqueNameStream::MAX_ATTEMPTS` times in a row.
+/// Once that budget is spent the reachable names are treated as exhausted and the stream only yields `None` from then on.
+/// Generation failures are yielded as `Some(Err(...))` and do not end the stream.
+pub struct UniqueNameStream<'a, const N: usize> {
+    experiments: &'a NameExperiments<N>,
+    hard_stop: Option<u8>,
+    yielded: HashSet<String>,
+    exhausted: bool,
+}
+
+impl<const N: usize> UniqueNameStream<'_, N> {
+    /// The number of repeated (or empty) names generated in a row before the stream is exhausted
+    pub const MAX_ATTEMPTS: usize = 1000;
+
+    /// The number of names yielded so far
+    pub fn yielded_count(&self) -> usize {
+        self.yielded.len()
+    }
+    /// Checks if the stream has given up on finding a new name
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+}
+
+impl<const N: usize> Iterator for UniqueNameStream<'_, N> {
+    type Item = Result<String, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+        for _ in 0..Self::MAX_ATTEMPTS {
+            match self.experiments.build_random_name(self.hard_stop) {
+                Err(e) => return Some(Err(e)),
+                Ok(name) if !name.is_empty() && !self.yielded.contains(&name) => {
+                    self.yielded.insert(name.clone());
+                    return Some(Ok(name));
+                },
+                Ok(_) => {},
+            }
+        }
+        self.exhausted = true;
+        None
+    }
+}
+
+impl<const N: usize> NameExperiments<N> {
+    /// Produces names as with `build_random_name`, but never the same name twice. Intended for naming a large population (e.g. every NPC in a game world) without duplicates.
+    /// See `UniqueNameStream` for how exhaustion is signalled.
+    ///
+    /// ## Parameters
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    pub fn unique_name_stream(&self, hard_stop: Option<u8>) -> UniqueNameStream<'_, N> {
+        UniqueNameStream {
+            experiments: self,
+            hard_stop,
+            yielded: HashSet::new(),
+            exhausted: false,
+        }
+    }
+    /// Generates up to `count` distinct names, as with `build_random_name`, while generating no more than `total_attempt_budget` names across the whole batch.
+    /// Repeated (or empty) names count towards the budget, which puts a predictable upper bound on the work done for a batch even when few new names can be reached.
+    /// 
+    /// Produces the names in the order they were generated, paired with `true` if the budget ran out before `count` names were found. An Err is produced if generation itself produces an Err.
+    /// 
+    /// ## Parameters
+    /// * count: The number of distinct names wanted
+    /// * total_attempt_budget: The most names generated for the whole batch
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    pub fn build_random_names_unique(&self, count: usize, total_attempt_budget: usize, hard_stop: Option<u8>) -> Result<(Vec<String>, bool), String> {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut names: Vec<String> = Vec::with_capacity(count.min(total_attempt_budget));
+        for _ in 0..total_attempt_budget {
+            if names.len() == count {break;}
+            let name = self.build_random_name(hard_stop)?;
+            if !name.is_empty() && seen.insert(name.clone()) {
+                names.push(name);
+            }
+        }
+        let stopped_early = names.len() < count;
+        Ok((names, stopped_early))
+    }
+    /// Behaves like `build_random_names`, but a name that was already generated for the batch (or an empty name) is rejected and another name is generated in its place.
+    /// Gives up once `max_attempts` names have been generated for the whole batch, producing the distinct names found so far rather than an Err.
+    /// Use `build_random_names_unique` to also learn whether the batch came up short.
+    /// 
+    /// ## Parameters
+    /// * count: The number of distinct names wanted
+    /// * max_attempts: The most names generated for the whole batch
+    /// * hard_stop: An optional parameter to apply a strict control the number of characters produced. Defaults to `16` if `None` is provided
+    pub fn build_distinct_names(&self, count: usize, max_attempts: usize, hard_stop: Option<u8>) -> Result<Vec<String>, String> {
+        self.build_random_names_unique(count, max_attempts, hard_stop).map(|(names, _)| names)
+    }
+    /// Estimates how many distinct names can plausibly be produced by `build_random_name` with default settings, to judge whether a large number of unique names is achievable.
+    /// `sample_size` names are generated and the total is extrapolated with the [Chao1 estimator](https://en.wikipedia.org/wiki/Mark_and_recapture), from the number of names
+    /// generated exactly once and exactly twice. The estimate varies between calls and grows more reliable with larger samples.
+    /// 
+    /// Names that fail part way through and empty names are not counted. Produces `0.0` if no name could be generated.
+    pub fn estimated_unique_capacity(&self, sample_size: usize) -> f64 {
+        let mut tally: HashMap<String, usize> = HashMap::new();
+        for _ in 0..sample_size {
+            if let Ok(name) = self.build_random_name(None) {
+                if !name.is_empty() {
+                    *tally.entry(name).or_insert(0) += 1;
+                }
+            }
+        }
+        let observed = tally.len() as f64;
+        let singletons = tally.values().filter(|&&count| count == 1).count() as f64;
+        let doubletons = tally.values().filter(|&&count| count == 2).count() as f64;
+        // The bias corrected form keeps the estimate finite when no name was generated exactly twice
+        if doubletons > 0.0 {
+            observed + (singletons * singletons) / (2.0 * doubletons)
+        } else {
+            observed + singletons * (singletons - 1.0) / 2.0
+        }
+    }
+}
+
+impl<'a, const N: usize> InferenceModel<'a, N> {
+    /// See `NameExperiments::unique_name_stream`
+    pub fn unique_name_stream(&self, hard_stop: Option<u8>) -> UniqueNameStream<'a, N> {
+        self.experiments.unique_name_stream(hard_stop)
+    }
+    /// See `NameExperiments::build_random_names_unique`
+    pub fn build_random_names_unique(&self, count: usize, total_attempt_budget: usize, hard_stop: Option<u8>) -> Result<(Vec<String>, bool), String> {
+        self.experiments.build_random_names_unique(count, total_attempt_budget, hard_stop)
+    }
+    /// See `NameExperiments::build_distinct_names`
+    pub fn build_distinct_names(&self, count: usize, max_attempts: usize, hard_stop: Option<u8>) -> Result<Vec<String>, String> {
+        self.experiments.build_distinct_names(count, max_attempts, hard_stop)
+    }
+    /// See `NameExperiments::estimated_unique_capacity`
+    pub fn estimated_unique_capacity(&self, sample_size: usize) -> f64 {
+        self.experiments.estimated_unique_capacity(sample_size)
+    }
+}
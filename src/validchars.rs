@@ -2,6 +2,7 @@
 /// An enum of character to make rust better use of pattern matching in code elsewhere. 
 #[derive(Debug,Clone,Copy, PartialEq, Eq)]
 #[repr(u8)]
+#[allow(non_camel_case_types)]
 pub enum ValidChar {
     /// a
     a=0,
@@ -59,15 +60,19 @@ pub enum ValidChar {
     dash=26,
     /// apostrophe
     apostrophe=27,
+    /// A syllable boundary. Written as '|' in annotated names, e.g. "gruk|thar". Never included in generated names
+    syllable_break=28,
+    /// A space within a compound name, e.g. "iron maul"
+    space=29,
     /// null
-    null=28
+    null=30
 }
 
 pub const VALID_CHAR_COUNT: usize = ValidChar::VARIANTCOUNT as usize;
 
 impl ValidChar {
     /// A helper constant to track the count of characters that are considered valid in the system.
-    pub const VARIANTCOUNT: u8 = 29;
+    pub const VARIANTCOUNT: u8 = 31;
     /// A helper constant to quickly index valid characters
     pub const ALLCHARS: [ValidChar; VALID_CHAR_COUNT] = [
         ValidChar::a,
@@ -98,22 +103,68 @@ impl ValidChar {
         ValidChar::z,
         ValidChar::dash,
         ValidChar::apostrophe,
+        ValidChar::syllable_break,
+        ValidChar::space,
         ValidChar::null
     ];
+    /// The character used to mark a syllable boundary in annotated names
+    pub const SYLLABLE_BREAK: char = '|';
+    /// The character written in place of `ValidChar::null` in text. Within a `Name` or a sample it is equivalent to `None` and ends the name
+    pub const TERMINATOR: char = '_';
+
+    /// Folds an accented Latin letter (from the Latin-1 Supplement and Latin Extended-A blocks, e.g. 'é', 'ñ', 'ü', 'ø', 'å' or 'ł') into its base letter. Other characters are produced unchanged.
+    /// Only lowercase letters are folded. Letters without a single base letter (e.g. 'æ' or 'ß') are not folded.
+    pub fn fold_diacritic(c: char) -> char {
+        match c {
+            'à'..='å' | 'ā' | 'ă' | 'ą' => 'a',
+            'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+            'ð' | 'ď' | 'đ' => 'd',
+            'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+            'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+            'ĥ' | 'ħ' => 'h',
+            'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => 'i',
+            'ĵ' => 'j',
+            'ķ' => 'k',
+            'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => 'l',
+            'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+            'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => 'o',
+            'ŕ' | 'ŗ' | 'ř' => 'r',
+            'ś' | 'ŝ' | 'ş' | 'š' => 's',
+            'ţ' | 'ť' | 'ŧ' => 't',
+            'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+            'ŵ' => 'w',
+            'ý' | 'ÿ' | 'ŷ' => 'y',
+            'ź' | 'ż' | 'ž' => 'z',
+            _ => c,
+        }
+    }
+    /// Checks if `c` stands for the end of a name. True for `ValidChar::TERMINATOR` and for `'\0'` (the `char` form of `ValidChar::null`)
+    pub fn is_terminator(c: char) -> bool {
+        c == Self::TERMINATOR || c == char::from(Self::null)
+    }
 }
 
+/// Conversion is case insensitive. A character is lowercased using its full Unicode lowercase mapping before it is matched. Characters whose lowercase mapping expands into more
+/// than one character (e.g. 'İ' lowercases to "i̇") are rejected with an Err rather than being truncated to the first character of the expansion.
+/// An accented Latin letter is then folded into its base letter (see `ValidChar::fold_diacritic`), so "José" and "Zoë" are read as "jose" and "zoe".
 impl TryFrom<&char> for ValidChar {
     type Error=String;
     fn try_from(c: &char) -> Result<Self, String> {
-        let input_char = c.to_lowercase().next().unwrap();
+        let mut lowercase = c.to_lowercase();
+        let input_char = match (lowercase.next(), lowercase.next()) {
+            (Some(l), None) => Self::fold_diacritic(l),
+            _ => return Err(format!("{c} has no single character lowercase form"))
+        };
         let early_res = match input_char {
             '-' => Some(Self::dash),
             '\'' => Some(Self::apostrophe),
+            Self::SYLLABLE_BREAK => Some(Self::syllable_break),
+            ' ' => Some(Self::space),
             '\0' => Some(Self::null),
             _ => None
         };
         if let Some(res) = early_res {return Ok(res);}
-        let c_ident = c.to_lowercase().next().unwrap() as u32 - 'a' as u32;
+        let c_ident = (input_char as u32).wrapping_sub('a' as u32);
         match c_ident {
             0 => Ok(Self::a),
             1 => Ok(Self::b),
@@ -151,6 +202,8 @@ impl From<ValidChar> for char {
         match value {
             ValidChar::apostrophe => '\'',
             ValidChar::dash => '-',
+            ValidChar::syllable_break => ValidChar::SYLLABLE_BREAK,
+            ValidChar::space => ' ',
             ValidChar::null => '\0',
             _ => char::from_u32(value as u32 + 'a' as u32).unwrap()
         }
@@ -166,7 +219,9 @@ impl TryFrom<u8> for ValidChar {
             ),
             26 => Ok(ValidChar::dash),
             27 => Ok(ValidChar::apostrophe),
-            28 => Ok(ValidChar::null),
+            28 => Ok(ValidChar::syllable_break),
+            29 => Ok(ValidChar::space),
+            30 => Ok(ValidChar::null),
             _ => Err(format!("{value} is an invalid character"))
         }
     }